@@ -3,14 +3,9 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 fn criterion_benchmark(c: &mut Criterion) {
     macro_rules! bench_day {
         ($day:tt) => {{
-            const INPUT: &str = include_str!(concat!(
-                std::env!("AOC_CACHE"),
-                "/2024_",
-                stringify!($day),
-                ".txt"
-            ));
+            let input = advent_of_code_2024::real_input(stringify!($day)).unwrap();
             c.bench_function(stringify!($day), |b| {
-                b.iter(|| advent_of_code_2024::days::$day::solve(black_box(INPUT)))
+                b.iter(|| advent_of_code_2024::days::$day::solve(black_box(&input)))
             });
             // const INPUT_TEST: &str =
             //     include_str!(concat!("../test_input/", stringify!($day), ".txt"));
@@ -0,0 +1,164 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{config::Config, vault::Vault, DayResult};
+
+/// Known & last seen answers for each day, stored alongside the cached inputs
+pub struct AnswerStore {
+    dir: PathBuf,
+    year: i32,
+    vault: Vault,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    /// There are no known answers to compare against
+    Unknown,
+    Matched,
+    Mismatched,
+}
+
+impl From<&DayResult> for Recorded {
+    fn from(result: &DayResult) -> Self {
+        Self {
+            part1: result.part1.as_ref().map(|a| a.to_string()),
+            part2: result.part2.as_ref().map(|a| a.to_string()),
+            recorded_at: Utc::now(),
+        }
+    }
+}
+
+impl Recorded {
+    /// Compares these answers against `known`, ignoring parts with no known answer
+    pub fn compare(&self, known: &Recorded) -> Comparison {
+        let parts = [(&self.part1, &known.part1), (&self.part2, &known.part2)];
+        if parts.iter().all(|(_, known)| known.is_none()) {
+            return Comparison::Unknown;
+        }
+        if parts
+            .iter()
+            .any(|(actual, known)| known.is_some() && actual != known)
+        {
+            return Comparison::Mismatched;
+        }
+
+        Comparison::Matched
+    }
+}
+
+impl AnswerStore {
+    pub fn new(config: &Config, year: i32) -> Result<Self> {
+        Ok(Self {
+            dir: config.cache_dir()?,
+            year,
+            vault: Vault::new(config),
+        })
+    }
+
+    fn file_name(&self, day: &str, kind: &str) -> String {
+        format!("{year}_{day}.{kind}.json", year = self.year)
+    }
+
+    fn path(&self, day: &str, kind: &str) -> PathBuf {
+        self.dir.join(self.file_name(day, kind))
+    }
+
+    /// The known answers, from the vault if they aren't cached
+    pub fn known(&self, day: &str) -> Result<Option<Recorded>> {
+        if let Some(known) = self.read(self.path(day, "answers"))? {
+            return Ok(Some(known));
+        }
+
+        let name = self.file_name(day, "answers");
+        self.vault
+            .read(&name)?
+            .map(|contents| {
+                serde_json::from_str(&contents)
+                    .with_context(|| format!("failed to parse {name} from the vault"))
+            })
+            .transpose()
+    }
+
+    /// Known answers that are only in the vault can't be read
+    pub fn vault_is_locked(&self) -> bool {
+        self.vault.is_locked()
+    }
+
+    pub fn last_run(&self, day: &str) -> Result<Option<Recorded>> {
+        self.read(self.path(day, "last_run"))
+    }
+
+    pub fn record_known(&self, day: &str, answers: &Recorded) -> Result<()> {
+        self.write(self.path(day, "answers"), answers)
+    }
+
+    pub fn record_run(&self, day: &str, answers: &Recorded) -> Result<()> {
+        self.write(self.path(day, "last_run"), answers)
+    }
+
+    fn read<T: DeserializeOwned>(&self, path: PathBuf) -> Result<Option<T>> {
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("failed to read {path:?}")),
+        };
+
+        serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("failed to parse {path:?}"))
+    }
+
+    fn write<T: Serialize>(&self, path: PathBuf, value: &T) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).context("failed to create answer directory")?;
+        let contents = serde_json::to_string_pretty(value).context("failed to serialise")?;
+        std::fs::write(&path, contents).with_context(|| format!("failed to write {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{Comparison, Recorded};
+
+    fn recorded(part1: Option<&str>, part2: Option<&str>) -> Recorded {
+        Recorded {
+            part1: part1.map(str::to_string),
+            part2: part2.map(str::to_string),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn compares_known_parts_only() {
+        let run = recorded(Some("11"), Some("31"));
+        assert_eq!(Comparison::Unknown, run.compare(&recorded(None, None)));
+        assert_eq!(
+            Comparison::Matched,
+            run.compare(&recorded(Some("11"), None))
+        );
+        assert_eq!(
+            Comparison::Matched,
+            run.compare(&recorded(Some("11"), Some("31")))
+        );
+        assert_eq!(
+            Comparison::Mismatched,
+            run.compare(&recorded(Some("11"), Some("32")))
+        );
+        assert_eq!(
+            Comparison::Mismatched,
+            recorded(Some("11"), None).compare(&recorded(None, Some("31")))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use aoc_common::config::Config;
+
+use crate::PackageName;
+
+/// Bodies adventofcode.com has been known to serve in place of an input, which must never be cached
+const ERROR_PAGE_SIGNATURES: &[&str] = &[
+    "Please don't repeatedly request this endpoint before it unlocks!",
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+    "404 Not Found",
+    "500 Internal Server Error",
+    "<!DOCTYPE html>",
+    "<html",
+];
+
+pub struct InputCache {
+    folder: PathBuf,
+    year: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct InputMeta {
+    pub year: i32,
+    pub day: u32,
+    pub downloaded_at: DateTime<Utc>,
+    pub len: usize,
+    pub sha256: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Missing,
+    Valid,
+    /// The input predates metadata sidecars so can only be checked for error pages
+    Unverified,
+    Invalid(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Missing => write!(f, "missing"),
+            Verdict::Valid => write!(f, "ok"),
+            Verdict::Unverified => write!(f, "unverified (no metadata)"),
+            Verdict::Invalid(reason) => write!(f, "invalid: {reason}"),
+        }
+    }
+}
+
+impl InputCache {
+    pub fn new(config: &Config, year: i32) -> anyhow::Result<Self> {
+        Ok(Self {
+            folder: config.cache_dir()?,
+            year,
+        })
+    }
+
+    pub fn input_path(&self, pkg_name: PackageName) -> PathBuf {
+        self.folder
+            .join(format!("{year}_{pkg_name}.txt", year = self.year))
+    }
+
+    pub fn meta_path(&self, pkg_name: PackageName) -> PathBuf {
+        self.folder
+            .join(format!("{year}_{pkg_name}.meta.json", year = self.year))
+    }
+
+    pub fn store(&self, pkg_name: PackageName, body: &str) -> anyhow::Result<()> {
+        check_body(body).context("refusing to cache response")?;
+
+        if let Err(err) = std::fs::create_dir(&self.folder) {
+            if err.kind() != ErrorKind::AlreadyExists {
+                return Err(err).context("failed to create aoc cache directory");
+            }
+        } else {
+            info!("created {folder:?}", folder = self.folder)
+        }
+
+        let input_path = self.input_path(pkg_name);
+        std::fs::write(&input_path, body.as_bytes())
+            .context("failed to write aoc input to cache")?;
+        self.write_meta(pkg_name, body, Utc::now())?;
+        info!("cached input to {input_path:?}");
+
+        Ok(())
+    }
+
+    /// Writes a sidecar for an input cached before sidecars existed, provided it looks sane
+    pub fn adopt(&self, pkg_name: PackageName) -> anyhow::Result<()> {
+        let input_path = self.input_path(pkg_name);
+        let body = std::fs::read_to_string(&input_path).context("failed to read cached input")?;
+        check_body(&body).context("refusing to adopt cached input")?;
+        let modified = std::fs::metadata(&input_path)
+            .and_then(|meta| meta.modified())
+            .context("failed to read cached input modification time")?;
+
+        self.write_meta(pkg_name, &body, modified.into())
+    }
+
+    fn write_meta(
+        &self,
+        pkg_name: PackageName,
+        body: &str,
+        downloaded_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let meta = InputMeta {
+            year: self.year,
+            day: pkg_name.0,
+            downloaded_at,
+            len: body.len(),
+            sha256: sha256(body),
+        };
+        let meta = serde_json::to_string_pretty(&meta).context("failed to serialise metadata")?;
+        std::fs::write(self.meta_path(pkg_name), meta).context("failed to write metadata")?;
+
+        Ok(())
+    }
+
+    pub fn verify(&self, pkg_name: PackageName) -> anyhow::Result<Verdict> {
+        let body = match std::fs::read_to_string(self.input_path(pkg_name)) {
+            Ok(body) => body,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Verdict::Missing),
+            Err(err) => return Err(err).context("failed to read cached input"),
+        };
+        if let Err(err) = check_body(&body) {
+            return Ok(Verdict::Invalid(err.to_string()));
+        }
+
+        let meta = match std::fs::read_to_string(self.meta_path(pkg_name)) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Verdict::Unverified),
+            Err(err) => return Err(err).context("failed to read metadata"),
+        };
+        let meta: InputMeta = match serde_json::from_str(&meta) {
+            Ok(meta) => meta,
+            Err(err) => return Ok(Verdict::Invalid(format!("unreadable metadata: {err}"))),
+        };
+
+        if (meta.year, meta.day) != (self.year, pkg_name.0) {
+            return Ok(Verdict::Invalid(format!(
+                "metadata is for {year} day {day}",
+                year = meta.year,
+                day = meta.day
+            )));
+        }
+        if meta.len != body.len() {
+            return Ok(Verdict::Invalid(format!(
+                "expected {expected} bytes, found {actual}",
+                expected = meta.len,
+                actual = body.len()
+            )));
+        }
+        if meta.sha256 != sha256(&body) {
+            return Ok(Verdict::Invalid("content hash mismatch".to_string()));
+        }
+
+        Ok(Verdict::Valid)
+    }
+
+    pub fn remove(&self, pkg_name: PackageName) -> anyhow::Result<()> {
+        for path in [self.input_path(pkg_name), self.meta_path(pkg_name)] {
+            if let Err(err) = std::fs::remove_file(&path) {
+                if err.kind() != ErrorKind::NotFound {
+                    return Err(err).with_context(|| format!("failed to remove {path:?}"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_body(body: &str) -> anyhow::Result<()> {
+    if body.is_empty() {
+        bail!("body is empty");
+    }
+    if let Some(signature) = ERROR_PAGE_SIGNATURES
+        .iter()
+        .find(|signature| body.contains(*signature))
+    {
+        bail!("body looks like an error page ({signature:?})");
+    }
+    // every input ends in a newline, so a missing one means the download was cut short
+    if !body.ends_with('\n') {
+        bail!("body has no trailing newline, it may be truncated");
+    }
+
+    Ok(())
+}
+
+fn sha256(body: &str) -> String {
+    Sha256::digest(body.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_body, InputCache, Verdict};
+    use crate::PackageName;
+
+    fn temp_cache(name: &str) -> InputCache {
+        let folder = std::env::temp_dir().join(format!("aoc-cache-test-{name}"));
+        let _ = std::fs::remove_dir_all(&folder);
+        InputCache { folder, year: 2024 }
+    }
+
+    #[test]
+    fn rejects_error_pages() {
+        assert!(check_body("1 2 3\n").is_ok());
+        assert!(check_body("").is_err());
+        assert!(check_body("1 2 3").is_err());
+        assert!(check_body(
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let cache = temp_cache("tampering");
+        let day = PackageName(3);
+        assert_eq!(Verdict::Missing, cache.verify(day).unwrap());
+
+        cache.store(day, "mul(2,4)\n").unwrap();
+        assert_eq!(Verdict::Valid, cache.verify(day).unwrap());
+
+        std::fs::write(cache.input_path(day), "mul(2,5)\n").unwrap();
+        assert!(matches!(cache.verify(day).unwrap(), Verdict::Invalid(_)));
+
+        std::fs::remove_file(cache.meta_path(day)).unwrap();
+        assert_eq!(Verdict::Unverified, cache.verify(day).unwrap());
+        cache.adopt(day).unwrap();
+        assert_eq!(Verdict::Valid, cache.verify(day).unwrap());
+
+        cache.remove(day).unwrap();
+        assert_eq!(Verdict::Missing, cache.verify(day).unwrap());
+    }
+}
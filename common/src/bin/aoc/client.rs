@@ -0,0 +1,156 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use reqwest::{
+    blocking::{Client, ClientBuilder},
+    StatusCode,
+};
+use tracing::{info, warn};
+
+use aoc_common::config::Config;
+
+/// The longest wait between retries, however many there have been
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// A session-authenticated adventofcode.com client that spaces out & retries its requests
+pub struct AocClient {
+    client: Client,
+    session: String,
+    min_interval: Duration,
+    max_retries: u32,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl AocClient {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let session = config.session()?;
+        if config.contact.is_none() {
+            warn!("no contact in aoc.toml, adventofcode.com asks for one in the user agent");
+        }
+
+        let client = ClientBuilder::new()
+            .user_agent(config.user_agent())
+            .build()
+            .context("failed to build http client")?;
+
+        Ok(Self {
+            client,
+            session,
+            min_interval: Duration::from_secs(config.fetch.min_interval_secs),
+            max_retries: config.fetch.max_retries,
+            last_request: Cell::new(None),
+        })
+    }
+
+    pub fn get(&self, url: &str) -> anyhow::Result<String> {
+        let mut attempt = 0;
+        loop {
+            match self.try_get(url) {
+                Ok(body) => return Ok(body),
+                Err(Failure::Permanent(err)) => return Err(err),
+                Err(Failure::Transient(err)) if attempt >= self.max_retries => {
+                    return Err(err.context(format!("gave up after {attempt} retries")))
+                }
+                Err(Failure::Transient(err)) => {
+                    let backoff = backoff(self.min_interval, attempt);
+                    attempt += 1;
+                    warn!("request failed ({err:#}), retry {attempt} in {backoff:?}");
+                    std::thread::sleep(backoff);
+                }
+            }
+        }
+    }
+
+    fn try_get(&self, url: &str) -> Result<String, Failure> {
+        self.throttle();
+        info!("retrieving {url}");
+
+        let request = self
+            .client
+            .get(url)
+            .header(
+                "Cookie",
+                format!("session={session}", session = self.session),
+            )
+            .build()
+            .context("failed to build http request")
+            .map_err(Failure::Permanent)?;
+
+        let response = self.client.execute(request).map_err(|err| {
+            let transient = err.is_timeout() || err.is_connect();
+            Failure::new(
+                transient,
+                anyhow!(err).context("failed to perform http request"),
+            )
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let transient = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+            return Err(Failure::new(
+                transient,
+                anyhow!("bad http response code returned: {status}"),
+            ));
+        }
+
+        response
+            .text()
+            .context("failed to read http response body")
+            .map_err(Failure::Transient)
+    }
+
+    fn throttle(&self) {
+        if let Some(last) = self.last_request.get() {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+}
+
+enum Failure {
+    Transient(anyhow::Error),
+    Permanent(anyhow::Error),
+}
+
+impl Failure {
+    fn new(transient: bool, err: anyhow::Error) -> Self {
+        if transient {
+            Failure::Transient(err)
+        } else {
+            Failure::Permanent(err)
+        }
+    }
+}
+
+/// Doubles from `min_interval` (at least a second) with each attempt, up to `MAX_BACKOFF`
+fn backoff(min_interval: Duration, attempt: u32) -> Duration {
+    let base = min_interval.max(Duration::from_secs(1));
+    2_u32
+        .checked_pow(attempt)
+        .and_then(|factor| base.checked_mul(factor))
+        .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{backoff, MAX_BACKOFF};
+
+    #[test]
+    fn backs_off_exponentially_up_to_a_cap() {
+        let secs = |s| Duration::from_secs(s);
+        assert_eq!(secs(5), backoff(secs(5), 0));
+        assert_eq!(secs(20), backoff(secs(5), 2));
+        assert_eq!(secs(4), backoff(Duration::ZERO, 2));
+        assert_eq!(MAX_BACKOFF, backoff(secs(5), 10));
+        assert_eq!(MAX_BACKOFF, backoff(secs(5), 40));
+        assert_eq!(MAX_BACKOFF, backoff(secs(u64::MAX), 3));
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use toml_edit::{value, Array, DocumentMut, InlineTable};
+
+use aoc_common::config::{Config, CONFIG_FILE};
+
+use crate::relative_path;
+
+/// The crate holding the shared infrastructure every year's crate depends on
+const COMMON_DIR: &str = "common";
+
+/// The crate behind `run`, which links every year's solvers
+const RUNNER_DIR: &str = "runner";
+
+const LIB: &str = r#"pub use aoc_common::*;
+
+pub mod days;
+
+/// Solves `day` for `input`, for callers that only know the day at runtime
+pub fn solve_day(day: u8, input: &str) -> anyhow::Result<DayResult> {
+    days::REGISTRY.solve(day, input)
+}
+
+/// The days with a solver, in ascending order
+pub fn available_days() -> Vec<u8> {
+    days::REGISTRY.days()
+}
+"#;
+
+const DAYS_MOD: &str = r#"use aoc_common::Registry;
+
+/// Every day's solver by number, kept in step with the modules above by `aoc`
+pub const REGISTRY: Registry = Registry(&[
+]);
+"#;
+
+/// Generates a workspace member for `year` holding only its days, returning its directory
+pub fn init(config: &Config, year: i32, dir: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    let root = config.workspace_root();
+    if let Some(existing) = config.years()?.into_iter().find(|c| c.year == year) {
+        bail!("{year} already lives in {root:?}", root = existing.root);
+    }
+    let common = root.join(COMMON_DIR);
+    if !common.join("Cargo.toml").is_file() {
+        bail!("no shared crate at {common:?}");
+    }
+
+    let member = dir.unwrap_or_else(|| PathBuf::from(format!("aoc-{year}")));
+    let crate_dir = root.join(&member);
+    if crate_dir.exists() {
+        bail!("{crate_dir:?} already exists");
+    }
+
+    let aoc_toml = read(&root.join(CONFIG_FILE))?;
+    let workspace_manifest = read(&root.join("Cargo.toml"))?;
+    let common_path = relative_path(&member, Path::new(COMMON_DIR));
+
+    write(&crate_dir.join("Cargo.toml"), &manifest(year, &common_path))?;
+    write(&crate_dir.join(CONFIG_FILE), &year_config(&aoc_toml, year)?)?;
+    write(&crate_dir.join("src/lib.rs"), LIB)?;
+    write(&crate_dir.join("src/days/mod.rs"), DAYS_MOD)?;
+    for dir in ["src/bin", "test_input"] {
+        std::fs::create_dir_all(crate_dir.join(dir))
+            .with_context(|| format!("failed to create {dir}"))?;
+    }
+    write(
+        &root.join("Cargo.toml"),
+        &add_member(&workspace_manifest, &member)?,
+    )?;
+
+    let runner = root.join(RUNNER_DIR);
+    if runner.join("Cargo.toml").is_file() {
+        let crate_path = relative_path(Path::new(RUNNER_DIR), &member);
+        let runner_manifest = read(&runner.join("Cargo.toml"))?;
+        let years = read(&runner.join("src/years.rs"))?;
+        write(
+            &runner.join("Cargo.toml"),
+            &add_runner_dependency(&runner_manifest, year, &crate_path)?,
+        )?;
+        write(
+            &runner.join("src/years.rs"),
+            &add_runner_year(&years, year)?,
+        )?;
+    }
+
+    Ok(crate_dir)
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("failed to read {path:?}"))
+}
+
+fn write(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("failed to create {parent:?}"))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("failed to write {path:?}"))
+}
+
+fn manifest(year: i32, common_path: &Path) -> String {
+    format!(
+        r#"[package]
+name = "advent-of-code-{year}"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = ["cli", "bench"]
+cli = ["aoc-common/cli"]
+bench = ["cli"]
+
+[dependencies]
+anyhow = "1.0.93"
+aoc-common = {{ path = "{common}", default-features = false }}
+
+[dev-dependencies]
+aoc-common = {{ path = "{common}", default-features = false, features = ["cli"] }}
+"#,
+        common = common_path.display()
+    )
+}
+
+/// The workspace's `aoc.toml` with the new year & crate, keeping the shared settings & comments
+fn year_config(source: &str, year: i32) -> anyhow::Result<String> {
+    let mut doc = source
+        .parse::<DocumentMut>()
+        .with_context(|| format!("failed to parse {CONFIG_FILE}"))?;
+    doc["year"] = value(i64::from(year));
+    doc["crate_name"] = value(format!("advent_of_code_{year}"));
+
+    Ok(doc.to_string())
+}
+
+fn add_member(source: &str, member: &Path) -> anyhow::Result<String> {
+    let mut doc = source
+        .parse::<DocumentMut>()
+        .context("failed to parse workspace Cargo.toml")?;
+    let Some(members) = doc
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+    else {
+        bail!("workspace Cargo.toml has no [workspace] members");
+    };
+    let member = member.to_string_lossy().replace('\\', "/");
+    if !members.iter().any(|m| m.as_str() == Some(&member)) {
+        push_keeping_layout(members, member);
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Pushes onto a one-member-per-line array without squashing it onto a single line
+fn push_keeping_layout(members: &mut Array, member: String) {
+    let decor = members
+        .iter()
+        .last()
+        .map(|last| last.decor().clone())
+        .unwrap_or_default();
+    members.push(member);
+    if let Some(pushed) = members.iter_mut().last() {
+        *pushed.decor_mut() = decor;
+    }
+}
+
+/// The runner's Cargo.toml depending on the new year's crate, solvers only
+fn add_runner_dependency(source: &str, year: i32, crate_path: &Path) -> anyhow::Result<String> {
+    let mut doc = source
+        .parse::<DocumentMut>()
+        .context("failed to parse the runner's Cargo.toml")?;
+    let Some(dependencies) = doc
+        .get_mut("dependencies")
+        .and_then(|dependencies| dependencies.as_table_like_mut())
+    else {
+        bail!("the runner's Cargo.toml has no [dependencies]");
+    };
+    let mut dependency = InlineTable::new();
+    dependency.insert(
+        "path",
+        crate_path.to_string_lossy().replace('\\', "/").into(),
+    );
+    dependency.insert("default-features", false.into());
+    dependencies.insert(&format!("advent-of-code-{year}"), value(dependency));
+    dependencies.sort_values();
+
+    Ok(doc.to_string())
+}
+
+/// The runner's `YEARS` with a line for the new year, kept in year order
+fn add_runner_year(source: &str, year: i32) -> anyhow::Result<String> {
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.contains("YEARS") && line.trim_end().ends_with("&["))
+    else {
+        bail!("runner/src/years.rs has no YEARS list");
+    };
+    let entries = lines[start + 1..]
+        .iter()
+        .take_while(|line| line.trim() != "];")
+        .map(|line| year_of(line))
+        .collect::<Vec<_>>();
+    if entries.contains(&Some(year)) {
+        return Ok(source.to_string());
+    }
+    let index = entries
+        .iter()
+        .position(|entry| entry.is_some_and(|y| y > year))
+        .unwrap_or(entries.len());
+    lines.insert(
+        start + 1 + index,
+        format!("    ({year}, &advent_of_code_{year}::days::REGISTRY),"),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn year_of(line: &str) -> Option<i32> {
+    line.trim()
+        .strip_prefix('(')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use toml_edit::DocumentMut;
+
+    use super::{add_member, add_runner_dependency, add_runner_year, manifest, year_config};
+
+    /// Each feature with what it turns on
+    fn features(manifest: &str) -> Vec<(String, Vec<String>)> {
+        let doc = manifest.parse::<DocumentMut>().unwrap();
+        doc["features"]
+            .as_table()
+            .unwrap()
+            .iter()
+            .map(|(name, enables)| {
+                let enables = enables.as_array().unwrap().iter();
+                let enables = enables.map(|f| f.as_str().unwrap().to_string());
+                (name.to_string(), enables.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_years_have_the_root_crates_features() {
+        let root = include_str!("../../../../Cargo.toml");
+        let generated = manifest(2025, Path::new("../common"));
+        assert_eq!(features(root), features(&generated));
+    }
+
+    #[test]
+    fn updates_year_and_crate_only() {
+        let source = "# comment\nyear = 2024\ncrate_name = \"advent_of_code_2024\"\n\n[fetch]\nmax_retries = 3\n";
+        assert_eq!(
+            "# comment\nyear = 2025\ncrate_name = \"advent_of_code_2025\"\n\n[fetch]\nmax_retries = 3\n",
+            year_config(source, 2025).unwrap()
+        );
+    }
+
+    #[test]
+    fn adds_workspace_member_once() {
+        let source = "[package]\nname = \"a\"\n\n[workspace]\nmembers = [\n    \"common\",\n]\n";
+        let added = add_member(source, Path::new("aoc-2025")).unwrap();
+        assert_eq!(
+            "[package]\nname = \"a\"\n\n[workspace]\nmembers = [\n    \"common\",\n    \"aoc-2025\",\n]\n",
+            added
+        );
+        assert_eq!(added, add_member(&added, Path::new("aoc-2025")).unwrap());
+        assert!(add_member("[package]\nname = \"a\"\n", Path::new("aoc-2025")).is_err());
+    }
+
+    #[test]
+    fn adds_runner_dependency() {
+        let source = "[package]\nname = \"aoc-runner\"\n\n[dependencies]\nadvent-of-code-2024 = { path = \"..\", default-features = false }\nanyhow = \"1.0.93\"\n";
+        assert_eq!(
+            "[package]\nname = \"aoc-runner\"\n\n[dependencies]\nadvent-of-code-2024 = { path = \"..\", default-features = false }\nadvent-of-code-2025 = { path = \"../aoc-2025\", default-features = false }\nanyhow = \"1.0.93\"\n",
+            add_runner_dependency(source, 2025, Path::new("../aoc-2025")).unwrap()
+        );
+    }
+
+    #[test]
+    fn adds_runner_years_in_order() {
+        let source = "pub const YEARS: &[(i32, &Registry)] = &[\n    (2024, &advent_of_code_2024::days::REGISTRY),\n];\n";
+        let added = add_runner_year(source, 2023).unwrap();
+        assert_eq!(
+            "pub const YEARS: &[(i32, &Registry)] = &[\n    (2023, &advent_of_code_2023::days::REGISTRY),\n    (2024, &advent_of_code_2024::days::REGISTRY),\n];\n",
+            added
+        );
+        assert_eq!(added, add_runner_year(&added, 2023).unwrap());
+        assert!(add_runner_year("", 2023).is_err());
+    }
+}
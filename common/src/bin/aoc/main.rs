@@ -0,0 +1,824 @@
+use anyhow::{anyhow, bail, Context};
+use chrono::Datelike;
+use clap::{parser::ValueSource, Args as _, CommandFactory, FromArgMatches, Parser, Subcommand};
+use tracing::{info, warn};
+
+use aoc_common::{
+    config::Config,
+    vault::{Vault, PASSPHRASE_ENV},
+};
+use cache::{InputCache, Verdict};
+use client::AocClient;
+use modfile::ModFile;
+use template::{Template, TemplateKind, Values};
+
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{ErrorKind, Write},
+    path::{Component, Path, PathBuf},
+};
+
+mod cache;
+mod client;
+mod init;
+mod modfile;
+mod stats;
+mod status;
+mod template;
+mod unlock;
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse_strict();
+    setup_tracing()?;
+    let config = match &args.profile {
+        Some(profile) => Config::discover()?.with_profile(profile)?,
+        None => Config::discover()?,
+    };
+    match args.command {
+        None => scaffold(&config, args.scaffold),
+        Some(Command::Wait(args)) => wait(&config, args),
+        Some(Command::Cache(CacheArgs {
+            command: CacheCommand::Verify { year, adopt },
+        })) => verify_cache(&config, year.unwrap_or(config.year), adopt),
+        Some(Command::Cache(CacheArgs {
+            command: CacheCommand::Refetch { day, year },
+        })) => refetch(&config, day, year.unwrap_or(config.year)),
+        Some(Command::Template(TemplateArgs {
+            command: TemplateCommand::Check,
+        })) => check_templates(&config),
+        Some(Command::Status(args)) => print_status(&config, args),
+        Some(Command::Init { year, dir }) => {
+            let crate_dir = init::init(&config, year, dir)?;
+            println!(
+                "created {year} in {crate_dir:?}, run `aoc <day>` from inside it to scaffold days"
+            );
+            Ok(())
+        }
+        Some(Command::Remove { day }) => remove(&config, PackageName(day)),
+        Some(Command::Fetch(args)) => fetch(&config, args),
+        Some(Command::Stats(args)) => print_stats(&config, args),
+        Some(Command::Vault(VaultArgs {
+            command: VaultCommand::Seal { year },
+        })) => seal_vault(&config, year.unwrap_or(config.year)),
+        Some(Command::Vault(VaultArgs {
+            command: VaultCommand::Open { year },
+        })) => open_vault(&config, year.unwrap_or(config.year)),
+    }
+}
+
+fn scaffold(config: &Config, args: ScaffoldArgs) -> anyhow::Result<()> {
+    let year = args.year.unwrap_or(config.year);
+    unlock::ensure_unlocked(year, args.day)?;
+    let pkg_name = PackageName(args.day);
+    if !args.download_only {
+        let values = template_values(config, pkg_name, year, &args);
+        write_runner_file(config, pkg_name, &values, args.overwrite)
+            .context("could not write runner")?;
+        update_mod_file(config, pkg_name).context("could not update mod file")?;
+        write_solver_file(config, pkg_name, &values).context("could not write solver file")?;
+        ensure_test_file(config, pkg_name, &values, args.overwrite)
+            .context("failed to make test file")?;
+    }
+    ensure_cached_input(config, pkg_name, year).context("could not ensure cached input")?;
+    Ok(())
+}
+
+fn wait(config: &Config, args: WaitArgs) -> anyhow::Result<()> {
+    let (year, day) = match (args.year, args.day) {
+        (Some(year), Some(day)) => (year, day),
+        (year, day) => {
+            let (next_year, next_day) = unlock::next_unlock(unlock::aoc_now());
+            (year.unwrap_or(next_year), day.unwrap_or(next_day))
+        }
+    };
+    unlock::wait_for_unlock(year, day)?;
+    scaffold(
+        config,
+        ScaffoldArgs {
+            day,
+            year: Some(year),
+            overwrite: args.overwrite,
+            download_only: args.download_only,
+            example_part1: None,
+            example_part2: None,
+        },
+    )
+}
+
+fn verify_cache(config: &Config, year: i32, adopt: bool) -> anyhow::Result<()> {
+    let cache = InputCache::new(config, year)?;
+    let mut bad = 0;
+    for day in 1..=unlock::last_day(year) {
+        let pkg_name = PackageName(day);
+        let mut verdict = cache.verify(pkg_name)?;
+        if verdict == Verdict::Missing {
+            continue;
+        }
+        if adopt && verdict == Verdict::Unverified {
+            cache.adopt(pkg_name)?;
+            verdict = cache.verify(pkg_name)?;
+        }
+        if matches!(verdict, Verdict::Invalid(_)) {
+            bad += 1;
+        }
+        println!("{year} {pkg_name}: {verdict}");
+    }
+
+    if bad > 0 {
+        bail!("{bad} cached inputs are invalid, repair them with `aoc cache refetch <day>`");
+    }
+
+    Ok(())
+}
+
+/// Whether a cache file is one `aoc vault` keeps: inputs, their metadata & known answers
+fn is_vaulted(year: i32, name: &str) -> bool {
+    name.starts_with(&format!("{year}_day"))
+        && [".txt", ".meta.json", ".answers.json"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+}
+
+fn unlock_vault(config: &Config) -> anyhow::Result<Vault> {
+    let vault = Vault::new(config);
+    if std::env::var_os(PASSPHRASE_ENV).is_some() {
+        return Ok(vault);
+    }
+
+    let passphrase =
+        rpassword::prompt_password("vault passphrase: ").context("failed to read passphrase")?;
+    if !vault.exists() {
+        let confirm = rpassword::prompt_password("confirm new vault passphrase: ")
+            .context("failed to read passphrase")?;
+        if passphrase != confirm {
+            bail!("passphrases did not match");
+        }
+    }
+
+    Ok(vault.with_passphrase(passphrase))
+}
+
+fn seal_vault(config: &Config, year: i32) -> anyhow::Result<()> {
+    let cache_dir = config.cache_dir()?;
+    let mut names = std::fs::read_dir(&cache_dir)
+        .with_context(|| format!("failed to list {cache_dir:?}"))?
+        .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+        .filter(|name| name.as_ref().map_or(true, |name| is_vaulted(year, name)))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("failed to list {cache_dir:?}"))?;
+    names.sort_unstable();
+
+    let vault = unlock_vault(config)?;
+    let mut sealed = 0;
+    for name in &names {
+        let path = cache_dir.join(name);
+        let contents =
+            std::fs::read_to_string(&path).with_context(|| format!("failed to read {path:?}"))?;
+        if vault.write(name, &contents)? {
+            info!("sealed {name}");
+            sealed += 1;
+        }
+    }
+    println!(
+        "sealed {sealed} changed of {total} cached files into {dir:?}",
+        total = names.len(),
+        dir = config.vault_dir()
+    );
+
+    Ok(())
+}
+
+fn open_vault(config: &Config, year: i32) -> anyhow::Result<()> {
+    let cache_dir = config.cache_dir()?;
+    std::fs::create_dir_all(&cache_dir)
+        .with_context(|| format!("failed to create {cache_dir:?}"))?;
+
+    let vault = unlock_vault(config)?;
+    let mut opened = 0;
+    for name in vault.names()? {
+        let path = cache_dir.join(&name);
+        if !is_vaulted(year, &name) || path.exists() {
+            continue;
+        }
+        let contents = vault
+            .read(&name)?
+            .with_context(|| format!("{name} vanished from the vault"))?;
+        std::fs::write(&path, contents).with_context(|| format!("failed to write {path:?}"))?;
+        info!("opened {name}");
+        opened += 1;
+    }
+    println!("opened {opened} files missing from {cache_dir:?}");
+
+    Ok(())
+}
+
+fn check_templates(config: &Config) -> anyhow::Result<()> {
+    let mut bad = 0;
+    for template in Template::load_all(config)? {
+        let name = template.kind.file_name();
+        let source = &template.source;
+        match template.check() {
+            Ok(()) => println!("{name} ({source}): ok"),
+            Err(err) => {
+                bad += 1;
+                println!("{name} ({source}): {err:#}");
+            }
+        }
+    }
+
+    if bad > 0 {
+        bail!("{bad} templates are invalid");
+    }
+
+    Ok(())
+}
+
+fn print_status(config: &Config, args: StatusArgs) -> anyhow::Result<()> {
+    let year = args.year.unwrap_or(config.year);
+    let stars = if args.stars {
+        let page = AocClient::new(config)?.get(&format!("https://adventofcode.com/{year}"))?;
+        Some(status::parse_stars(&page)?)
+    } else {
+        None
+    };
+    let days = status::collect(config, year, &declared_days(config)?, stars.as_ref())?;
+    if days.iter().any(|day| day.answers_locked) {
+        warn!("the vault is locked, set {PASSPHRASE_ENV} to see the answers sealed in it");
+    }
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&days).context("failed to serialise status")?;
+        println!("{json}");
+    } else {
+        print!("{}", status::render_table(&days));
+    }
+
+    Ok(())
+}
+
+fn print_stats(config: &Config, args: StatsArgs) -> anyhow::Result<()> {
+    let year = args.year.unwrap_or(config.year);
+    let cached = match args.refresh {
+        true => None,
+        false => stats::load_cached(config, year)?,
+    };
+    let stats = match cached {
+        Some(stats) => stats,
+        None => {
+            let url = format!("https://adventofcode.com/{year}/leaderboard/self");
+            let page = AocClient::new(config)?.get(&url)?;
+            let stats = stats::Stats {
+                year,
+                fetched_at: chrono::Utc::now(),
+                days: stats::parse(&page).with_context(|| format!("failed to read {url}"))?,
+            };
+            if stats.days.is_empty() {
+                warn!("no days attempted in {year} yet, not caching the stats");
+            } else {
+                stats::store(config, &stats)?;
+            }
+            stats
+        }
+    };
+
+    print!("{}", stats::summarise(&stats));
+    if let Some(csv) = args.csv {
+        std::fs::write(&csv, stats::to_csv(&stats))
+            .with_context(|| format!("failed to write {csv:?}"))?;
+        info!("wrote csv to {csv:?}");
+    }
+
+    Ok(())
+}
+
+/// Undoes a scaffold, leaving the cached input in place
+fn remove(config: &Config, pkg_name: PackageName) -> anyhow::Result<()> {
+    let mut mod_file = read_mod_file(config)?;
+    if mod_file.remove(pkg_name) {
+        std::fs::write(mod_file_path(config), mod_file.render())
+            .context("failed to write mod file")?;
+        info!("removed {pkg_name} from mod.rs");
+    }
+
+    for path in [
+        config.days_dir().join(format!("{pkg_name}.rs")),
+        config.bins_dir().join(format!("{pkg_name}.rs")),
+        config.test_inputs_dir().join(format!("{pkg_name}.txt")),
+    ] {
+        match std::fs::remove_file(&path) {
+            Ok(()) => info!("removed {path:?}"),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("failed to remove {path:?}")),
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads every requested day that is unlocked & not yet cached
+fn fetch(config: &Config, args: FetchArgs) -> anyhow::Result<()> {
+    let year = args.year.unwrap_or(config.year);
+    let days = match &args.selection.days {
+        Some(days) => parse_days(days, year)?,
+        None => (1..=unlock::last_day(year)).collect(),
+    };
+    let cache = InputCache::new(config, year)?;
+    let mut client = None;
+
+    for day in days {
+        let pkg_name = PackageName(day);
+        if unlock::time_until_unlock(year, day)?.is_some() {
+            info!("{year} {pkg_name} is still locked, stopping");
+            break;
+        }
+        match cache.verify(pkg_name)? {
+            Verdict::Missing => {}
+            Verdict::Invalid(reason) => {
+                warn!("{year} {pkg_name} is cached but {reason}, repair it with `aoc cache refetch {day}`");
+                continue;
+            }
+            Verdict::Valid | Verdict::Unverified => continue,
+        }
+
+        let client = match &mut client {
+            Some(client) => client,
+            None => client.insert(AocClient::new(config)?),
+        };
+        retrieve_and_cache_fresh_input(client, pkg_name, year, &cache)
+            .with_context(|| format!("failed to fetch {year} {pkg_name}"))?;
+    }
+
+    Ok(())
+}
+
+/// Parses a day list such as `1-25` or `1,3,5-7`, which must all be days of `year`
+fn parse_days(days: &str, year: i32) -> anyhow::Result<BTreeSet<u32>> {
+    let last = unlock::last_day(year);
+    let mut parsed = BTreeSet::new();
+    for part in days.split(',') {
+        let part = part.trim();
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let start: u32 = start
+            .trim()
+            .parse()
+            .with_context(|| format!("bad day in {part:?}"))?;
+        let end: u32 = end
+            .trim()
+            .parse()
+            .with_context(|| format!("bad day in {part:?}"))?;
+        if start == 0 || end > last || start > end {
+            bail!("bad day range {part:?}, expected {year} days between 1-{last}");
+        }
+        parsed.extend(start..=end);
+    }
+
+    Ok(parsed)
+}
+
+fn refetch(config: &Config, day: u32, year: i32) -> anyhow::Result<()> {
+    unlock::ensure_unlocked(year, day)?;
+    let pkg_name = PackageName(day);
+    let cache = InputCache::new(config, year)?;
+    let client = AocClient::new(config)?;
+    cache.remove(pkg_name)?;
+    retrieve_and_cache_fresh_input(&client, pkg_name, year, &cache)
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true
+)]
+struct Args {
+    /// The aoc.toml profile whose session & cache to use
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    scaffold: ScaffoldArgs,
+}
+
+impl Args {
+    fn parse_strict() -> Self {
+        Self::try_parse_strict(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Parses like `try_parse_from`, but rejects scaffolding arguments given alongside a
+    /// subcommand, which would otherwise be dropped. `--profile` is still allowed before one
+    fn try_parse_strict<I, T>(argv: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(argv)?;
+        if let Some(subcommand) = matches.subcommand_name() {
+            let scaffold = ScaffoldArgs::augment_args(clap::Command::new("scaffold"));
+            for arg in scaffold.get_arguments() {
+                if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+                    let name = match (arg.get_long(), arg.get_short()) {
+                        (Some(long), _) => format!("--{long}"),
+                        (None, Some(short)) => format!("-{short}"),
+                        (None, None) => format!("<{}>", arg.get_id().as_str().to_uppercase()),
+                    };
+                    return Err(command.error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        format!("{name} can't be used with the `{subcommand}` subcommand"),
+                    ));
+                }
+            }
+        }
+
+        Self::from_arg_matches(&matches)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Count down to a puzzle unlocking, then scaffold & download it
+    Wait(WaitArgs),
+    /// Check or repair cached inputs
+    Cache(CacheArgs),
+    /// Work with the solver, runner & test input scaffolding templates
+    Template(TemplateArgs),
+    /// Show the state of every day in the calendar
+    Status(StatusArgs),
+    /// Summarise your solve times & ranks from the personal leaderboard
+    Stats(StatsArgs),
+    /// Download every unlocked input that isn't cached yet, politely
+    Fetch(FetchArgs),
+    /// Encrypt cached inputs & answers into a vault that can be committed, or decrypt them back
+    Vault(VaultArgs),
+    /// Generate a crate for a new year in this workspace, sharing the common infrastructure
+    Init {
+        /// The year to generate
+        year: i32,
+        /// Where to put the crate, relative to the workspace root, defaults to `aoc-<year>`
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Delete a day's solver, runner & example input and remove it from mod.rs
+    Remove {
+        /// The day to remove
+        day: u32,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct FetchArgs {
+    #[command(flatten)]
+    selection: DaySelection,
+    /// The year to fetch, defaults to the year in aoc.toml
+    #[arg(short, long)]
+    year: Option<i32>,
+}
+
+#[derive(clap::Args, Debug)]
+#[group(required = true, multiple = false)]
+struct DaySelection {
+    /// Fetch every day of the year
+    #[arg(long)]
+    all: bool,
+    /// The days to fetch, e.g. `1-25` or `1,3,5-7`
+    #[arg(long)]
+    days: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct StatsArgs {
+    /// The year to summarise, defaults to the year in aoc.toml
+    #[arg(short, long)]
+    year: Option<i32>,
+    /// Fetch the leaderboard again rather than using the cached copy
+    #[arg(short, long, default_value_t = false)]
+    refresh: bool,
+    /// Also export the stats as csv to this path
+    #[arg(long)]
+    csv: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct StatusArgs {
+    /// The year to show, defaults to the year in aoc.toml
+    #[arg(short, long)]
+    year: Option<i32>,
+    /// Print the status as json instead of a table
+    #[arg(long, default_value_t = false)]
+    json: bool,
+    /// Include your stars from the adventofcode.com event page
+    #[arg(short, long, default_value_t = false)]
+    stars: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct TemplateArgs {
+    #[command(subcommand)]
+    command: TemplateCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum TemplateCommand {
+    /// Render every template with example values to check its placeholders
+    Check,
+}
+
+#[derive(clap::Args, Debug)]
+struct VaultArgs {
+    #[command(subcommand)]
+    command: VaultCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum VaultCommand {
+    /// Encrypt the year's cached inputs, metadata & known answers into the vault
+    Seal {
+        /// The year to seal, defaults to the year in aoc.toml
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
+    /// Decrypt the year's files from the vault into the cache, keeping any already cached
+    Open {
+        /// The year to open, defaults to the year in aoc.toml
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct CacheArgs {
+    #[command(subcommand)]
+    command: CacheCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Check every cached input for the year against its metadata & known error pages
+    Verify {
+        /// The year to verify, defaults to the year in aoc.toml
+        #[arg(short, long)]
+        year: Option<i32>,
+        /// Write metadata for clean inputs cached before metadata was recorded
+        #[arg(long, default_value_t = false)]
+        adopt: bool,
+    },
+    /// Discard a cached input and download it again
+    Refetch {
+        /// The day to refetch
+        day: u32,
+        /// The year to refetch, defaults to the year in aoc.toml
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct ScaffoldArgs {
+    /// The day to download, defaults to today in the AoC timezone (UTC-5)
+    #[arg(default_value_t = unlock::aoc_now().day())]
+    day: u32,
+    /// The year to download, defaults to the year in aoc.toml
+    #[arg(short, long)]
+    year: Option<i32>,
+    /// Whether existing runner & test input files should be overwritten
+    #[arg(short, long, default_value_t = false)]
+    overwrite: bool,
+    /// Only download input from adventofcode.com (if not already cached)
+    #[arg(short, default_value_t = false)]
+    download_only: bool,
+    /// The example's part 1 answer, for templates using `{{example_part1}}`
+    #[arg(long)]
+    example_part1: Option<String>,
+    /// The example's part 2 answer, for templates using `{{example_part2}}`
+    #[arg(long, requires = "example_part1")]
+    example_part2: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct WaitArgs {
+    /// The day to wait for, defaults to the next one to unlock
+    day: Option<u32>,
+    /// The year to wait for, defaults to the year of the next unlock
+    #[arg(short, long)]
+    year: Option<i32>,
+    /// Whether existing runner & test input files should be overwritten
+    #[arg(short, long, default_value_t = false)]
+    overwrite: bool,
+    /// Only download input from adventofcode.com once unlocked
+    #[arg(short, default_value_t = false)]
+    download_only: bool,
+}
+
+fn setup_tracing() -> Result<(), anyhow::Error> {
+    tracing_subscriber::fmt()
+        .try_init()
+        .map_err(|err| anyhow!("failed to setup tracing: {}", err))?;
+
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct PackageName(u32);
+
+impl Display for PackageName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{:0>2}", self.0)
+    }
+}
+
+fn write_runner_file(
+    config: &Config,
+    pkg_name: PackageName,
+    values: &Values,
+    overwrite: bool,
+) -> Result<(), anyhow::Error> {
+    let runner = Template::load(config, TemplateKind::Runner)?.render(values)?;
+    File::options()
+        .create_new(!overwrite)
+        .write(true)
+        .truncate(true)
+        .open(config.bins_dir().join(format!("{pkg_name}.rs")))
+        .context("runner file already exists")?
+        .write_all(runner.as_bytes())
+        .context("failed to write runner file")?;
+
+    Ok(())
+}
+
+fn mod_file_path(config: &Config) -> PathBuf {
+    config.days_dir().join("mod.rs")
+}
+
+fn read_mod_file(config: &Config) -> anyhow::Result<ModFile> {
+    let source =
+        std::fs::read_to_string(mod_file_path(config)).context("failed to read mod file")?;
+    Ok(ModFile::parse(&source))
+}
+
+fn declared_days(config: &Config) -> anyhow::Result<BTreeSet<PackageName>> {
+    Ok(read_mod_file(config)?.days())
+}
+
+fn update_mod_file(config: &Config, pkg_name: PackageName) -> Result<(), anyhow::Error> {
+    let mut mod_file = read_mod_file(config)?;
+    if mod_file.insert(pkg_name) {
+        std::fs::write(mod_file_path(config), mod_file.render())
+            .context("failed to write mod file")?;
+    }
+
+    Ok(())
+}
+
+fn template_values(
+    config: &Config,
+    pkg_name: PackageName,
+    year: i32,
+    args: &ScaffoldArgs,
+) -> Values {
+    let test_input = relative_path(
+        &config.paths.days,
+        &config.paths.test_inputs.join(format!("{pkg_name}.txt")),
+    );
+    Values {
+        pkg_name,
+        year,
+        crate_name: config.crate_name.clone(),
+        test_input: test_input.display().to_string(),
+        example_part1: args.example_part1.clone(),
+        example_part2: args.example_part2.clone(),
+    }
+}
+
+fn write_solver_file(
+    config: &Config,
+    pkg_name: PackageName,
+    values: &Values,
+) -> Result<(), anyhow::Error> {
+    let solver = Template::load(config, TemplateKind::Solver)?.render(values)?;
+    std::fs::write(
+        config.days_dir().join(format!("{pkg_name}.rs")),
+        solver.as_bytes(),
+    )
+    .context("failed to write solver file")?;
+
+    Ok(())
+}
+
+/// The path to `to` from within `from`, where both are relative to the same root
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    from.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .map(|_| Path::new(".."))
+        .chain(std::iter::once(to))
+        .collect()
+}
+
+fn ensure_cached_input(config: &Config, pkg_name: PackageName, year: i32) -> anyhow::Result<()> {
+    let cache = InputCache::new(config, year)?;
+
+    match cache.verify(pkg_name)? {
+        Verdict::Valid => {
+            info!("serving cached input");
+            return Ok(());
+        }
+        Verdict::Unverified => {
+            warn!("cached input has no metadata, run `aoc cache verify --adopt` to check it");
+            return Ok(());
+        }
+        Verdict::Invalid(reason) => bail!(
+            "cached input is {reason}, run `aoc cache refetch {day}` to repair it",
+            day = pkg_name.0
+        ),
+        Verdict::Missing => {}
+    }
+
+    let client = AocClient::new(config)?;
+    retrieve_and_cache_fresh_input(&client, pkg_name, year, &cache)
+}
+
+fn retrieve_and_cache_fresh_input(
+    client: &AocClient,
+    pkg_name: PackageName,
+    year: i32,
+    cache: &InputCache,
+) -> anyhow::Result<()> {
+    let url = format!(
+        "https://adventofcode.com/{year}/day/{day}/input",
+        day = pkg_name.0
+    );
+    let response = client.get(&url)?;
+    info!("retrieved input");
+    cache.store(pkg_name, &response)?;
+
+    Ok(())
+}
+
+fn ensure_test_file(
+    config: &Config,
+    pkg_name: PackageName,
+    values: &Values,
+    overwrite: bool,
+) -> anyhow::Result<()> {
+    let test_input = Template::load(config, TemplateKind::TestInput)?.render(values)?;
+    File::options()
+        .create_new(!overwrite)
+        .write(true)
+        .truncate(true)
+        .open(config.test_inputs_dir().join(format!("{pkg_name}.txt")))
+        .context("failed to make test file")?
+        .write_all(test_input.as_bytes())
+        .context("failed to write test file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::{parse_days, Args, Command};
+
+    #[test]
+    fn scaffold_args_conflict_with_subcommands() {
+        Args::command().debug_assert();
+        let err = Args::try_parse_strict(["aoc", "-d", "status"]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("-d can't be used with the `status` subcommand"),
+            "{err}"
+        );
+        let err = Args::try_parse_strict(["aoc", "5", "fetch", "--all"]).unwrap_err();
+        assert!(err.to_string().contains("<DAY> can't be used"), "{err}");
+        assert!(Args::try_parse_strict(["aoc", "-d", "5", "status"]).is_err());
+
+        let args = Args::try_parse_strict(["aoc", "--profile", "alt", "status"]).unwrap();
+        assert!(matches!(args.command, Some(Command::Status(_))));
+        let args = Args::try_parse_strict(["aoc", "-d", "5"]).unwrap();
+        assert!(args.command.is_none());
+        assert_eq!((5, true), (args.scaffold.day, args.scaffold.download_only));
+    }
+
+    #[test]
+    fn parses_day_lists() {
+        assert_eq!(
+            vec![1, 3, 5, 6, 7],
+            parse_days("1,3,5-7", 2024)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(25, parse_days("1-25", 2024).unwrap().len());
+        assert_eq!(12, parse_days("1-12", 2025).unwrap().len());
+        assert!(parse_days("13", 2025).is_err());
+        assert!(parse_days("0-3", 2024).is_err());
+        assert!(parse_days("5-3", 2024).is_err());
+        assert!(parse_days("26", 2024).is_err());
+        assert!(parse_days("x", 2024).is_err());
+    }
+}
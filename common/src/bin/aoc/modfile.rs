@@ -0,0 +1,329 @@
+use std::collections::BTreeSet;
+
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{space0, space1},
+    combinator::{all_consuming, map, opt, rest},
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::PackageName;
+
+/// The days module file, edited line by line so anything that isn't a day module survives untouched.
+/// Each day module has an entry in the `REGISTRY` of solvers, if the file has one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModFile {
+    lines: Vec<String>,
+}
+
+impl ModFile {
+    pub fn parse(source: &str) -> Self {
+        Self {
+            lines: source.lines().map(str::to_string).collect(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = self.lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    pub fn days(&self) -> BTreeSet<PackageName> {
+        self.day_lines().map(|(_, day)| day).collect()
+    }
+
+    /// Declares & registers `day` next to its neighbours, returning false if it is already declared
+    pub fn insert(&mut self, day: PackageName) -> bool {
+        if self.day_lines().any(|(_, existing)| existing == day) {
+            return false;
+        }
+
+        let index = self.sorted_index(self.day_lines(), day).unwrap_or_else(|| {
+            match self.registry_start() {
+                // keep the first module above the registry, which is declared after them
+                Some(registry) => {
+                    let index = self.attributes_start(registry);
+                    self.lines.insert(index, String::new());
+                    index
+                }
+                // keep new modules out of any trailing blank lines
+                None => self
+                    .lines
+                    .iter()
+                    .rposition(|line| !line.trim().is_empty())
+                    .map(|i| i + 1)
+                    .unwrap_or(0),
+            }
+        });
+        self.lines.insert(index, format!("pub mod {day};"));
+
+        if let Some(registry) = self.registry_start() {
+            let index = self
+                .sorted_index(self.registry_lines(), day)
+                .unwrap_or(registry + 1);
+            self.lines
+                .insert(index, format!("    ({n}, {day}::solve),", n = day.0));
+        }
+
+        true
+    }
+
+    /// Removes the declaration of `day`, any attributes on it & its registry entry,
+    /// returning false if it wasn't declared
+    pub fn remove(&mut self, day: PackageName) -> bool {
+        let entry = self
+            .registry_lines()
+            .find(|&(_, existing)| existing == day)
+            .map(|(i, _)| i);
+        if let Some(i) = entry {
+            self.lines.remove(i);
+        }
+
+        let Some(i) = self
+            .day_lines()
+            .find(|&(_, existing)| existing == day)
+            .map(|(i, _)| i)
+        else {
+            return false;
+        };
+
+        self.lines.drain(self.attributes_start(i)..=i);
+
+        true
+    }
+
+    /// Where `day` belongs among `existing`: after the previous day, or before the next day's attributes
+    fn sorted_index(
+        &self,
+        existing: impl Iterator<Item = (usize, PackageName)>,
+        day: PackageName,
+    ) -> Option<usize> {
+        let mut after = None;
+        let mut before = None;
+        for (i, existing) in existing {
+            if existing < day {
+                after = Some(i + 1);
+            } else if before.is_none() {
+                before = Some(self.attributes_start(i));
+            }
+        }
+
+        after.or(before)
+    }
+
+    fn registry_start(&self) -> Option<usize> {
+        self.lines
+            .iter()
+            .position(|line| line.contains("REGISTRY") && line.trim_end().ends_with("&["))
+    }
+
+    fn registry_lines(&self) -> impl Iterator<Item = (usize, PackageName)> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| parse_registry_line(line).ok().map(|(_, day)| (i, day)))
+    }
+
+    fn day_lines(&self) -> impl Iterator<Item = (usize, PackageName)> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| parse_mod_line(line).ok().map(|(_, day)| (i, day)))
+    }
+
+    /// The first line of the attributes & doc comments directly above line `i`
+    fn attributes_start(&self, i: usize) -> usize {
+        let attached = self.lines[..i]
+            .iter()
+            .rev()
+            .take_while(|line| {
+                let line = line.trim_start();
+                line.starts_with("#[") || line.starts_with("///")
+            })
+            .count();
+        i - attached
+    }
+}
+
+/// Matches `pub mod dayNN;`, allowing any visibility, spacing & a trailing comment
+fn parse_mod_line(input: &str) -> IResult<&str, PackageName> {
+    all_consuming(delimited(
+        tuple((
+            space0,
+            opt(terminated(
+                preceded(
+                    tag("pub"),
+                    opt(delimited(tag("("), take_until(")"), tag(")"))),
+                ),
+                space1,
+            )),
+            tag("mod"),
+            space1,
+        )),
+        map(
+            preceded(tag("day"), nom::character::complete::u32),
+            PackageName,
+        ),
+        tuple((space0, tag(";"), space0, opt(preceded(tag("//"), rest)))),
+    ))(input)
+}
+
+/// Matches `    (N, dayNN::solve),`
+fn parse_registry_line(input: &str) -> IResult<&str, PackageName> {
+    all_consuming(delimited(
+        tuple((
+            space0,
+            tag("("),
+            nom::character::complete::u32,
+            tag(","),
+            space0,
+        )),
+        map(
+            preceded(tag("day"), nom::character::complete::u32),
+            PackageName,
+        ),
+        tuple((tag("::solve),"), space0)),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModFile;
+    use crate::PackageName;
+
+    const SOURCE: &str = "//! solutions for each day
+pub mod grid;
+
+pub mod day01;
+#[cfg(feature = \"slow\")]
+/// brute force
+pub mod day03; // slow
+pub(crate) mod day04;
+
+mod helpers;
+";
+
+    #[test]
+    fn finds_only_day_modules() {
+        let days = ModFile::parse(SOURCE).days();
+        assert_eq!(
+            vec![PackageName(1), PackageName(3), PackageName(4)],
+            days.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn inserts_between_neighbours() {
+        let mut file = ModFile::parse(SOURCE);
+        assert!(file.insert(PackageName(2)));
+        assert!(file.insert(PackageName(5)));
+        assert!(!file.insert(PackageName(5)));
+        assert_eq!(
+            "//! solutions for each day
+pub mod grid;
+
+pub mod day01;
+pub mod day02;
+#[cfg(feature = \"slow\")]
+/// brute force
+pub mod day03; // slow
+pub(crate) mod day04;
+pub mod day05;
+
+mod helpers;
+",
+            file.render()
+        );
+    }
+
+    #[test]
+    fn inserts_before_first_day_and_into_empty_files() {
+        let mut file = ModFile::parse(SOURCE);
+        file.remove(PackageName(1));
+        assert!(file.insert(PackageName(2)));
+        assert!(file
+            .render()
+            .contains("pub mod day02;\n#[cfg(feature = \"slow\")]"));
+
+        let mut empty = ModFile::parse("pub mod grid;\n\n");
+        empty.insert(PackageName(1));
+        assert_eq!("pub mod grid;\npub mod day01;\n\n", empty.render());
+    }
+
+    #[test]
+    fn keeps_registry_in_step() {
+        let source = "use aoc_common::Registry;
+
+pub mod day01;
+pub mod day03;
+
+pub const REGISTRY: Registry = Registry(&[
+    (1, day01::solve),
+    (3, day03::solve),
+]);
+";
+        let mut file = ModFile::parse(source);
+        assert!(file.insert(PackageName(2)));
+        assert!(file.insert(PackageName(4)));
+        assert_eq!(
+            "use aoc_common::Registry;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+
+pub const REGISTRY: Registry = Registry(&[
+    (1, day01::solve),
+    (2, day02::solve),
+    (3, day03::solve),
+    (4, day04::solve),
+]);
+",
+            file.render()
+        );
+
+        file.remove(PackageName(2));
+        file.remove(PackageName(4));
+        assert_eq!(source, file.render());
+    }
+
+    #[test]
+    fn registers_first_day_above_registry() {
+        let mut file = ModFile::parse(
+            "use aoc_common::Registry;
+
+/// every day
+pub const REGISTRY: Registry = Registry(&[
+]);
+",
+        );
+        file.insert(PackageName(1));
+        assert_eq!(
+            "use aoc_common::Registry;
+
+pub mod day01;
+
+/// every day
+pub const REGISTRY: Registry = Registry(&[
+    (1, day01::solve),
+]);
+",
+            file.render()
+        );
+    }
+
+    #[test]
+    fn remove_undoes_insert() {
+        let mut file = ModFile::parse(SOURCE);
+        file.insert(PackageName(9));
+        assert!(file.remove(PackageName(9)));
+        assert!(!file.remove(PackageName(9)));
+        assert_eq!(SOURCE, file.render());
+
+        assert!(file.remove(PackageName(3)));
+        assert!(!file.render().contains("slow"));
+    }
+}
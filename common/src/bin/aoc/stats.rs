@@ -0,0 +1,368 @@
+use std::{
+    fmt::{Display, Formatter, Write},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aoc_common::config::Config;
+
+/// A year's personal times & ranks, as shown on `/{year}/leaderboard/self`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub year: i32,
+    pub fetched_at: DateTime<Utc>,
+    pub days: Vec<DayStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: u32,
+    pub part1: Option<PartStats>,
+    pub part2: Option<PartStats>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PartStats {
+    pub time: SolveTime,
+    pub rank: u32,
+    pub score: u32,
+}
+
+/// Time from unlock to solve, which AoC stops counting after a day
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveTime {
+    Seconds(u64),
+    OverADay,
+}
+
+impl Display for SolveTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveTime::Seconds(secs) => write!(
+                f,
+                "{h:0>2}:{m:0>2}:{s:0>2}",
+                h = secs / 3_600,
+                m = secs % 3_600 / 60,
+                s = secs % 60
+            ),
+            SolveTime::OverADay => write!(f, ">24h"),
+        }
+    }
+}
+
+impl SolveTime {
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        if s == ">24h" {
+            return Ok(SolveTime::OverADay);
+        }
+        let secs = s
+            .split(':')
+            .try_fold(0, |acc, part| part.parse::<u64>().map(|n| acc * 60 + n))
+            .with_context(|| format!("bad time {s:?}"))?;
+
+        Ok(SolveTime::Seconds(secs))
+    }
+
+    fn seconds(self) -> Option<u64> {
+        match self {
+            SolveTime::Seconds(secs) => Some(secs),
+            SolveTime::OverADay => None,
+        }
+    }
+}
+
+pub fn cache_path(config: &Config, year: i32) -> anyhow::Result<PathBuf> {
+    Ok(config.cache_dir()?.join(format!("{year}_stats.json")))
+}
+
+pub fn load_cached(config: &Config, year: i32) -> anyhow::Result<Option<Stats>> {
+    let path = cache_path(config, year)?;
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("failed to parse {path:?}")),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("failed to read {path:?}")),
+    }
+}
+
+pub fn store(config: &Config, stats: &Stats) -> anyhow::Result<()> {
+    let path = cache_path(config, stats.year)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("failed to create {parent:?}"))?;
+    }
+    let contents = serde_json::to_string_pretty(stats).context("failed to serialise stats")?;
+    std::fs::write(&path, contents).with_context(|| format!("failed to write {path:?}"))
+}
+
+/// Parses the `<pre>` table of the personal leaderboard, one row per attempted day. Anything
+/// outside the table is ignored, as are rows inside it that don't start with a day from 1 to 25.
+/// Fails if there's no table, e.g. a login page once the session has expired
+pub fn parse(page: &str) -> anyhow::Result<Vec<DayStats>> {
+    let table = page
+        .split_once("<pre")
+        .and_then(|(_, rest)| rest.split_once("</pre>"))
+        .map(|(table, _)| table)
+        .context("no leaderboard table on the page, has the session expired?")?;
+    let mut days = strip_tags(table)
+        .lines()
+        .filter_map(|line| {
+            let cells = line.split_whitespace().collect::<Vec<_>>();
+            let day = cells.first()?.parse::<u32>().ok()?;
+            (1..=25).contains(&day).then(|| {
+                parse_row(day, &cells[1..]).with_context(|| format!("bad row {:?}", line.trim()))
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    days.sort_unstable_by_key(|day| day.day);
+
+    Ok(days)
+}
+
+fn parse_row(day: u32, cells: &[&str]) -> anyhow::Result<DayStats> {
+    let part = |cells: &[&str]| -> anyhow::Result<Option<PartStats>> {
+        match cells {
+            ["-", "-", "-"] | [] => Ok(None),
+            [time, rank, score] => Ok(Some(PartStats {
+                time: SolveTime::parse(time)?,
+                rank: rank.parse().with_context(|| format!("bad rank {rank:?}"))?,
+                score: score
+                    .parse()
+                    .with_context(|| format!("bad score {score:?}"))?,
+            })),
+            _ => bail!("unexpected cells {cells:?}"),
+        }
+    };
+    if cells.len() != 3 && cells.len() != 6 {
+        bail!(
+            "day {day} has {len} cells, expected 3 or 6",
+            len = cells.len()
+        );
+    }
+
+    Ok(DayStats {
+        day,
+        part1: part(&cells[..3]).with_context(|| format!("bad part 1 for day {day}"))?,
+        part2: part(&cells[3..]).with_context(|| format!("bad part 2 for day {day}"))?,
+    })
+}
+
+fn strip_tags(page: &str) -> String {
+    let mut output = String::with_capacity(page.len());
+    let mut in_tag = false;
+    for c in page.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => output.push(c),
+            _ => {}
+        }
+    }
+    output.replace("&gt;", ">").replace("&lt;", "<")
+}
+
+pub fn to_csv(stats: &Stats) -> String {
+    let mut csv =
+        "day,part1_time_secs,part1_rank,part1_score,part2_time_secs,part2_rank,part2_score\n"
+            .to_string();
+    let cells = |part: Option<PartStats>| match part {
+        Some(PartStats { time, rank, score }) => {
+            let time = time
+                .seconds()
+                .map(|s| s.to_string())
+                .unwrap_or(">24h".to_string());
+            format!("{time},{rank},{score}")
+        }
+        None => ",,".to_string(),
+    };
+    for day in &stats.days {
+        writeln!(
+            csv,
+            "{day},{p1},{p2}",
+            day = day.day,
+            p1 = cells(day.part1),
+            p2 = cells(day.part2)
+        )
+        .expect("writing to a string cannot fail");
+    }
+
+    csv
+}
+
+pub fn summarise(stats: &Stats) -> String {
+    let mut out = String::new();
+    let opt = |part: Option<PartStats>, f: fn(PartStats) -> String| {
+        part.map(f).unwrap_or("-".to_string())
+    };
+
+    writeln!(
+        out,
+        "day  part 1 time  rank    part 2 time  rank    part 2 delta  rank trend"
+    )
+    .expect("writing to a string cannot fail");
+    let mut previous_rank = None;
+    for day in &stats.days {
+        let delta = match (day.part1, day.part2) {
+            (Some(p1), Some(p2)) => match (p1.time.seconds(), p2.time.seconds()) {
+                (Some(t1), Some(t2)) => SolveTime::Seconds(t2.saturating_sub(t1)).to_string(),
+                _ => ">24h".to_string(),
+            },
+            _ => "-".to_string(),
+        };
+        let rank = day.part2.or(day.part1).map(|part| part.rank);
+        let trend = match (previous_rank, rank) {
+            (Some(prev), Some(rank)) if rank < prev => "up",
+            (Some(prev), Some(rank)) if rank > prev => "down",
+            (Some(_), Some(_)) => "same",
+            _ => "-",
+        };
+        previous_rank = rank.or(previous_rank);
+
+        writeln!(
+            out,
+            "{day:<3}  {t1:<11}  {r1:<6}  {t2:<11}  {r2:<6}  {delta:<12}  {trend}",
+            day = day.day,
+            t1 = opt(day.part1, |p| p.time.to_string()),
+            r1 = opt(day.part1, |p| p.rank.to_string()),
+            t2 = opt(day.part2, |p| p.time.to_string()),
+            r2 = opt(day.part2, |p| p.rank.to_string()),
+        )
+        .expect("writing to a string cannot fail");
+    }
+
+    for (name, select) in [
+        (
+            "part 1",
+            (|d: &DayStats| d.part1) as fn(&DayStats) -> Option<PartStats>,
+        ),
+        ("part 2", |d: &DayStats| d.part2),
+    ] {
+        let solved = stats
+            .days
+            .iter()
+            .filter_map(|day| select(day).map(|part| (day.day, part)))
+            .collect::<Vec<_>>();
+        let fastest = solved.iter().min_by_key(|(_, part)| part.time);
+        let slowest = solved.iter().max_by_key(|(_, part)| part.time);
+        if let (Some((fast_day, fast)), Some((slow_day, slow))) = (fastest, slowest) {
+            writeln!(
+                out,
+                "{name}: fastest day {fast_day} ({fast_time}), slowest day {slow_day} ({slow_time})",
+                fast_time = fast.time,
+                slow_time = slow.time
+            )
+            .expect("writing to a string cannot fail");
+        }
+    }
+
+    let ranks = stats
+        .days
+        .iter()
+        .filter_map(|day| day.part2.or(day.part1).map(|part| part.rank as f64))
+        .collect::<Vec<_>>();
+    if ranks.len() >= 2 {
+        let (first, second) = ranks.split_at(ranks.len() / 2);
+        let mean = |ranks: &[f64]| ranks.iter().sum::<f64>() / ranks.len() as f64;
+        let (first, second) = (mean(first), mean(second));
+        let direction = if second < first {
+            "improving"
+        } else if second > first {
+            "worsening"
+        } else {
+            "steady"
+        };
+        writeln!(
+            out,
+            "rank trend: {direction}, mean rank {first:.0} in the first half vs {second:.0} in the second"
+        )
+        .expect("writing to a string cannot fail");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{parse, to_csv, PartStats, SolveTime, Stats};
+
+    fn days(page: &str) -> Vec<u32> {
+        parse(page).unwrap().iter().map(|d| d.day).collect()
+    }
+
+    const PAGE: &str = r#"<article><p>These are your personal leaderboard statistics.</p>
+<pre>      <span class="leaderboard-daydesc-first">--------Part 1--------</span>   <span class="leaderboard-daydesc-both">--------Part 2---------</span>
+Day   <span class="leaderboard-daydesc-first">    Time   Rank  Score</span>   <span class="leaderboard-daydesc-both">    Time   Rank  Score</span>
+ 11   00:05:12   1234      0   00:10:01   2345      0
+ 10       &gt;24h  40000      0       &gt;24h  38000      0
+  9   01:02:03   5000      0          -      -      -
+</pre>
+</article>"#;
+
+    #[test]
+    fn parses_leaderboard() {
+        let days = parse(PAGE).unwrap();
+        assert_eq!(
+            vec![9, 10, 11],
+            days.iter().map(|d| d.day).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(PartStats {
+                time: SolveTime::Seconds(3_723),
+                rank: 5_000,
+                score: 0
+            }),
+            days[0].part1
+        );
+        assert_eq!(None, days[0].part2);
+        assert_eq!(SolveTime::OverADay, days[1].part2.unwrap().time);
+        assert_eq!(2_345, days[2].part2.unwrap().rank);
+    }
+
+    #[test]
+    fn only_reads_day_rows_inside_the_table() {
+        let page = format!("<p>25 stars collected</p>\n2024\n{PAGE}\n<p>26 12:00:00 1 0</p>")
+            .replace(
+                "  9   01:02:03",
+                " 26   00:01:00   1      0\n  0   00:01:00   1      0\n  9   01:02:03",
+            );
+        assert_eq!(vec![9, 10, 11], days(&page));
+        assert_eq!(Vec::<u32>::new(), days("<pre>Day Time Rank Score\n</pre>"));
+    }
+
+    #[test]
+    fn fails_without_a_table_or_on_a_bad_row() {
+        let err = parse("<p>To play, please identify yourself</p>").unwrap_err();
+        assert!(err.to_string().contains("no leaderboard table"), "{err:#}");
+
+        let page = PAGE.replace("1234", "oops");
+        let err = parse(&page).unwrap_err();
+        assert!(
+            format!("{err:#}").contains(r#"bad row "11   00:05:12   oops"#),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn exports_csv() {
+        let stats = Stats {
+            year: 2024,
+            fetched_at: Utc::now(),
+            days: parse(PAGE).unwrap(),
+        };
+        assert_eq!(
+            "day,part1_time_secs,part1_rank,part1_score,part2_time_secs,part2_rank,part2_score
+9,3723,5000,0,,,
+10,>24h,40000,0,>24h,38000,0
+11,312,1234,0,601,2345,0
+",
+            to_csv(&stats)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Context;
+use regex::Regex;
+use serde::Serialize;
+
+use aoc_common::{
+    answers::{AnswerStore, Comparison},
+    config::Config,
+};
+
+use crate::{
+    cache::{InputCache, Verdict},
+    unlock, PackageName,
+};
+
+#[derive(Serialize, Debug)]
+pub struct DayStatus {
+    pub day: u32,
+    /// Declared in the days module
+    pub module: bool,
+    pub runner: bool,
+    pub input: String,
+    /// The example input has been filled in
+    pub example: bool,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// The known answers are sealed in the vault, which is locked, so they're unknown
+    pub answers_locked: bool,
+    /// How the last real input run compared to the known answers, if it has been run
+    pub last_run: Option<Comparison>,
+    pub stars: Option<u8>,
+}
+
+pub fn collect(
+    config: &Config,
+    year: i32,
+    modules: &BTreeSet<PackageName>,
+    stars: Option<&BTreeMap<u32, u8>>,
+) -> anyhow::Result<Vec<DayStatus>> {
+    let cache = InputCache::new(config, year)?;
+    let answers = AnswerStore::new(config, year)?;
+
+    (1..=unlock::last_day(year))
+        .map(|day| {
+            let pkg_name = PackageName(day);
+            let name = pkg_name.to_string();
+            let example = std::fs::metadata(config.test_inputs_dir().join(format!("{name}.txt")))
+                .map(|meta| meta.len() > 0)
+                .unwrap_or(false);
+            let (known, answers_locked) = match answers.known(&name) {
+                Ok(known) => (known, false),
+                Err(_) if answers.vault_is_locked() => (None, true),
+                Err(err) => return Err(err),
+            };
+            let last_run = answers.last_run(&name)?;
+            let last_run = last_run.map(|run| match &known {
+                Some(known) => run.compare(known),
+                None => Comparison::Unknown,
+            });
+            let (part1, part2) = known
+                .map(|known| (known.part1, known.part2))
+                .unwrap_or_default();
+
+            Ok(DayStatus {
+                day,
+                module: modules.contains(&pkg_name),
+                runner: config.bins_dir().join(format!("{name}.rs")).is_file(),
+                input: match cache.verify(pkg_name)? {
+                    Verdict::Invalid(_) => "invalid".to_string(),
+                    verdict => verdict.to_string(),
+                },
+                example,
+                part1,
+                part2,
+                answers_locked,
+                last_run,
+                stars: stars.map(|stars| stars.get(&day).cloned().unwrap_or_default()),
+            })
+        })
+        .collect()
+}
+
+/// Reads each day's stars from the calendar links on a year's event page
+pub fn parse_stars(page: &str) -> anyhow::Result<BTreeMap<u32, u8>> {
+    let pattern = Regex::new(r#"aria-label="Day (\d+)(?:, (one star|two stars))?""#)
+        .context("failed to compile stars regex")?;
+
+    pattern
+        .captures_iter(page)
+        .map(|captures| {
+            let day = captures[1].parse().context("failed to parse day")?;
+            let stars = match captures.get(2).map(|m| m.as_str()) {
+                Some("two stars") => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+            Ok((day, stars))
+        })
+        .collect()
+}
+
+pub fn render_table(days: &[DayStatus]) -> String {
+    let yes_no = |b: bool| if b { "yes" } else { "-" }.to_string();
+    let with_stars = days.iter().any(|day| day.stars.is_some());
+
+    let mut header = vec![
+        "day", "module", "runner", "input", "example", "part 1", "part 2", "last run",
+    ];
+    if with_stars {
+        header.push("stars");
+    }
+    let rows = days
+        .iter()
+        .map(|day| {
+            let answer = |part: &Option<String>| match (part, day.answers_locked) {
+                (Some(answer), _) => answer.clone(),
+                (None, true) => "locked".to_string(),
+                (None, false) => "-".to_string(),
+            };
+            let mut row = vec![
+                day.day.to_string(),
+                yes_no(day.module),
+                yes_no(day.runner),
+                day.input.clone(),
+                yes_no(day.example),
+                answer(&day.part1),
+                answer(&day.part2),
+                match day.last_run {
+                    None => "-",
+                    Some(Comparison::Unknown) if day.answers_locked => "locked",
+                    Some(Comparison::Unknown) => "no answers",
+                    Some(Comparison::Matched) => "matched",
+                    Some(Comparison::Mismatched) => "MISMATCH",
+                }
+                .to_string(),
+            ];
+            if let Some(stars) = day.stars {
+                row.push("*".repeat(stars as usize));
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain(std::iter::once(title.len()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    let mut push_row = |cells: &mut dyn Iterator<Item = &str>| {
+        let line = cells
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    };
+    push_row(&mut header.iter().copied());
+    for row in &rows {
+        push_row(&mut row.iter().map(String::as_str));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::answers::Comparison;
+
+    use super::{parse_stars, render_table, DayStatus};
+
+    #[test]
+    fn parses_calendar_stars() {
+        let page = r#"<a aria-label="Day 3, two stars" href="/2024/day/3" class="calendar-day3 calendar-verycomplete">
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">
+<a aria-label="Day 1" href="/2024/day/1" class="calendar-day1">"#;
+        let stars = parse_stars(page).unwrap();
+        assert_eq!(
+            vec![(1, 0), (2, 1), (3, 2)],
+            stars.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn shows_locked_answers_as_locked() {
+        let day = |day, answers_locked| DayStatus {
+            day,
+            module: true,
+            runner: true,
+            input: "valid".to_string(),
+            example: true,
+            part1: None,
+            part2: None,
+            answers_locked,
+            last_run: Some(Comparison::Unknown),
+            stars: None,
+        };
+        let table = render_table(&[day(1, true), day(2, false)]);
+        let rows = table
+            .lines()
+            .skip(1)
+            .map(|row| row.split_whitespace().collect::<Vec<_>>());
+        assert_eq!(
+            vec![
+                vec!["1", "yes", "yes", "valid", "yes", "locked", "locked", "locked"],
+                vec!["2", "yes", "yes", "valid", "yes", "-", "-", "no", "answers"],
+            ],
+            rows.collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use anyhow::{bail, Context};
+use strum::{EnumIter, IntoEnumIterator};
+
+use aoc_common::config::Config;
+
+use crate::PackageName;
+
+pub const PLACEHOLDERS: &[&str] = &[
+    "day",
+    "package",
+    "year",
+    "crate_name",
+    "test_input",
+    "example_part1",
+    "example_part2",
+    "example_answers",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumIter)]
+pub enum TemplateKind {
+    Solver,
+    Runner,
+    TestInput,
+}
+
+impl TemplateKind {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            TemplateKind::Solver => "solver.rs.tmpl",
+            TemplateKind::Runner => "runner.rs.tmpl",
+            TemplateKind::TestInput => "test_input.txt.tmpl",
+        }
+    }
+
+    fn built_in(self) -> &'static str {
+        match self {
+            TemplateKind::Solver => include_str!("templates/solver.rs.tmpl"),
+            TemplateKind::Runner => include_str!("templates/runner.rs.tmpl"),
+            TemplateKind::TestInput => include_str!("templates/test_input.txt.tmpl"),
+        }
+    }
+}
+
+pub enum Source {
+    Project(PathBuf),
+    BuiltIn,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Project(path) => write!(f, "{path}", path = path.display()),
+            Source::BuiltIn => write!(f, "built-in"),
+        }
+    }
+}
+
+pub struct Template {
+    pub kind: TemplateKind,
+    pub source: Source,
+    pub contents: String,
+}
+
+impl Template {
+    /// Loads the project's template for `kind`, falling back to the built-in one
+    pub fn load(config: &Config, kind: TemplateKind) -> anyhow::Result<Self> {
+        let path = config.templates_dir().join(kind.file_name());
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Ok(Self {
+                kind,
+                source: Source::Project(path),
+                contents,
+            }),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self {
+                kind,
+                source: Source::BuiltIn,
+                contents: kind.built_in().to_string(),
+            }),
+            Err(err) => Err(err).with_context(|| format!("failed to read template {path:?}")),
+        }
+    }
+
+    pub fn load_all(config: &Config) -> anyhow::Result<Vec<Self>> {
+        TemplateKind::iter()
+            .map(|kind| Self::load(config, kind))
+            .collect()
+    }
+
+    pub fn render(&self, values: &Values) -> anyhow::Result<String> {
+        render(&self.contents, values)
+            .with_context(|| format!("failed to render template {source}", source = self.source))
+    }
+
+    /// Renders the template with placeholder values & checks the result is usable
+    pub fn check(&self) -> anyhow::Result<()> {
+        let rendered = self.render(&Values::example())?;
+        if self.kind == TemplateKind::Solver && !rendered.contains("pub fn solve") {
+            bail!("solver template does not define `pub fn solve`");
+        }
+        if self.kind == TemplateKind::Runner && !rendered.contains("aoc!(") {
+            bail!("runner template does not invoke `aoc!`");
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Values {
+    pub pkg_name: PackageName,
+    pub year: i32,
+    pub crate_name: String,
+    pub test_input: String,
+    pub example_part1: Option<String>,
+    pub example_part2: Option<String>,
+}
+
+impl Values {
+    fn example() -> Self {
+        Self {
+            pkg_name: PackageName(1),
+            year: 2024,
+            crate_name: "advent_of_code_2024".to_string(),
+            test_input: "../../test_input/day01.txt".to_string(),
+            example_part1: Some("11".to_string()),
+            example_part2: Some("31".to_string()),
+        }
+    }
+
+    fn get(&self, placeholder: &str) -> Option<String> {
+        let value = match placeholder {
+            "day" => self.pkg_name.0.to_string(),
+            "package" => self.pkg_name.to_string(),
+            "year" => self.year.to_string(),
+            "crate_name" => self.crate_name.clone(),
+            "test_input" => self.test_input.clone(),
+            "example_part1" => self.example_part1.clone().unwrap_or_default(),
+            "example_part2" => self.example_part2.clone().unwrap_or_default(),
+            "example_answers" => match (&self.example_part1, &self.example_part2) {
+                (None, None) => "()".to_string(),
+                (Some(p1), None) => format!("({p1},)", p1 = answer_literal(p1)),
+                // the CLI requires part 1 with part 2, an empty string still compiles without it
+                (p1, Some(p2)) => format!(
+                    "({p1}, {p2})",
+                    p1 = answer_literal(p1.as_deref().unwrap_or_default()),
+                    p2 = answer_literal(p2)
+                ),
+            },
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+/// An answer as a Rust expression: integers as they are, anything else as a string literal
+fn answer_literal(answer: &str) -> String {
+    let answer = answer.trim();
+    if answer.parse::<i128>().is_ok() || answer.parse::<u128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// Substitutes every `{{placeholder}}` in `template`, failing on any it doesn't know.
+/// `{{{{` is written out as a literal `{{`
+fn render(template: &str, values: &Values) -> anyhow::Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        if rest[start..].starts_with("{{{{") {
+            output.push_str("{{");
+            rest = &rest[start + 4..];
+            continue;
+        }
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            let line = template[..template.len() - rest.len() + start]
+                .lines()
+                .count();
+            bail!("unterminated placeholder on line {line}");
+        };
+        let placeholder = after_open[..end].trim();
+        let Some(value) = values.get(placeholder) else {
+            bail!("unknown placeholder {{{{{placeholder}}}}}, expected one of {PLACEHOLDERS:?}");
+        };
+        output.push_str(&value);
+        rest = &after_open[end + 2..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::{render, TemplateKind, Values};
+    use crate::PackageName;
+
+    fn values() -> Values {
+        Values {
+            pkg_name: PackageName(7),
+            year: 2023,
+            crate_name: "aoc_2023".to_string(),
+            test_input: "../../test_input/day07.txt".to_string(),
+            example_part1: Some("3749".to_string()),
+            example_part2: None,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        assert_eq!(
+            "aoc_2023::aoc!(day07); // 7 2023 (3749,)",
+            render(
+                "{{crate_name}}::aoc!({{ package }}); // {{day}} {{year}} {{example_answers}}",
+                &values()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_placeholders() {
+        assert!(render("{{days}}", &values()).is_err());
+        assert!(render("fn main() {{ package", &values()).is_err());
+    }
+
+    #[test]
+    fn escapes_double_braces() {
+        assert_eq!(
+            r#"format!("{{}}", 7)"#,
+            render(r#"format!("{{{{}}", {{day}})"#, &values()).unwrap()
+        );
+    }
+
+    /// The elements of the tuple the solver template asserts the example against
+    fn example_answers(values: &Values) -> Vec<syn::Lit> {
+        let rendered = render(TemplateKind::Solver.built_in(), values).unwrap();
+        syn::parse_file(&rendered).expect("the solver should be valid Rust");
+        let tuple = render("{{example_answers}}", values).unwrap();
+        let syn::Expr::Tuple(parsed) = syn::parse_str(&tuple).unwrap() else {
+            panic!("{tuple} is not a tuple");
+        };
+        parsed
+            .elems
+            .into_iter()
+            .map(|elem| match elem {
+                syn::Expr::Lit(lit) => lit.lit,
+                _ => panic!("{tuple} has an answer that isn't a literal"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn solver_asserts_one_literal_per_answer() {
+        let mut values = values();
+        assert_eq!(1, example_answers(&values).len());
+        values.example_part1 = None;
+        assert!(example_answers(&values).is_empty());
+
+        values.example_part1 = Some("4,6,3".to_string());
+        values.example_part2 = Some("31".to_string());
+        let answers = example_answers(&values);
+        assert_eq!(2, answers.len());
+        assert!(matches!(&answers[0], syn::Lit::Str(s) if s.value() == "4,6,3"));
+        assert!(matches!(&answers[1], syn::Lit::Int(_)));
+
+        values.example_part1 = None;
+        let answers = example_answers(&values);
+        assert!(matches!(&answers[0], syn::Lit::Str(s) if s.value().is_empty()));
+    }
+
+    #[test]
+    fn built_ins_render() {
+        for kind in TemplateKind::iter() {
+            render(kind.built_in(), &values()).unwrap();
+        }
+    }
+}
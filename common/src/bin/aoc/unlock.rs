@@ -0,0 +1,164 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Datelike, FixedOffset, TimeDelta, TimeZone, Utc};
+use tracing::info;
+
+/// Puzzles unlock at midnight US Eastern, which AoC pins to UTC-5 for all of December
+const AOC_UTC_OFFSET_SECS: i32 = -5 * 60 * 60;
+
+/// The first year with the shorter, 12 day calendar
+const FIRST_SHORT_YEAR: i32 = 2025;
+
+/// Upper bound on the random delay added after an unlock, so we don't all hit the server at 00:00:00
+const MAX_JITTER: Duration = Duration::from_secs(5);
+
+pub fn aoc_timezone() -> FixedOffset {
+    FixedOffset::east_opt(AOC_UTC_OFFSET_SECS).expect("offset is within a day")
+}
+
+pub fn aoc_now() -> DateTime<FixedOffset> {
+    Utc::now().with_timezone(&aoc_timezone())
+}
+
+/// The last puzzle day of `year`'s calendar, which has 25 days up to 2024 & 12 since
+pub fn last_day(year: i32) -> u32 {
+    if year < FIRST_SHORT_YEAR {
+        25
+    } else {
+        12
+    }
+}
+
+pub fn unlock_time(year: i32, day: u32) -> anyhow::Result<DateTime<Utc>> {
+    let last = last_day(year);
+    if !(1..=last).contains(&day) {
+        bail!("day {day} is not a {year} advent of code day, expected 1-{last}");
+    }
+
+    let unlock = aoc_timezone()
+        .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+        .single()
+        .with_context(|| format!("no unlock time for {year} day {day}"))?;
+
+    Ok(unlock.with_timezone(&Utc))
+}
+
+pub fn time_until_unlock(year: i32, day: u32) -> anyhow::Result<Option<TimeDelta>> {
+    let remaining = unlock_time(year, day)? - Utc::now();
+    Ok((remaining > TimeDelta::zero()).then_some(remaining))
+}
+
+pub fn ensure_unlocked(year: i32, day: u32) -> anyhow::Result<()> {
+    if let Some(remaining) = time_until_unlock(year, day)? {
+        bail!(
+            "{year} day {day} unlocks in {remaining}, use `aoc wait` to fetch it as soon as it opens",
+            remaining = format_remaining(remaining)
+        );
+    }
+
+    Ok(())
+}
+
+/// The next puzzle to unlock after `now`
+pub fn next_unlock(now: DateTime<FixedOffset>) -> (i32, u32) {
+    let now = now.with_timezone(&aoc_timezone());
+    match (now.month(), now.day()) {
+        (12, day) if day < last_day(now.year()) => (now.year(), day + 1),
+        (12, _) => (now.year() + 1, 1),
+        _ => (now.year(), 1),
+    }
+}
+
+pub fn wait_for_unlock(year: i32, day: u32) -> anyhow::Result<()> {
+    while let Some(remaining) = time_until_unlock(year, day)? {
+        info!(
+            "{year} day {day} unlocks in {remaining}",
+            remaining = format_remaining(remaining)
+        );
+        let nap = if remaining > TimeDelta::hours(1) {
+            TimeDelta::minutes(15)
+        } else if remaining > TimeDelta::minutes(1) {
+            TimeDelta::minutes(1)
+        } else {
+            TimeDelta::seconds(1)
+        };
+        let nap = std::cmp::min(nap, remaining)
+            .to_std()
+            .context("failed to convert sleep duration")?;
+        std::thread::sleep(nap);
+    }
+
+    let jitter = jitter();
+    info!("unlocked, waiting a further {}ms", jitter.as_millis());
+    std::thread::sleep(jitter);
+
+    Ok(())
+}
+
+fn jitter() -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(random % MAX_JITTER.as_millis() as u64)
+}
+
+fn format_remaining(remaining: TimeDelta) -> String {
+    let secs = remaining.num_seconds();
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+    let (hours, secs) = (secs / 3_600, secs % 3_600);
+    let (mins, secs) = (secs / 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours:0>2}h {mins:0>2}m {secs:0>2}s")
+    } else {
+        format!("{hours:0>2}h {mins:0>2}m {secs:0>2}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use super::{aoc_timezone, format_remaining, last_day, next_unlock, unlock_time};
+
+    #[test]
+    fn unlocks_at_five_utc() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 12, 3, 5, 0, 0).unwrap(),
+            unlock_time(2024, 3).unwrap()
+        );
+        assert!(unlock_time(2024, 26).is_err());
+        assert!(unlock_time(2024, 0).is_err());
+        assert!(unlock_time(2025, 12).is_ok());
+        assert!(unlock_time(2025, 13).is_err());
+    }
+
+    #[test]
+    fn calendars_got_shorter_in_2025() {
+        assert_eq!(25, last_day(2015));
+        assert_eq!(25, last_day(2024));
+        assert_eq!(12, last_day(2025));
+    }
+
+    #[test]
+    fn next_unlock_crosses_years() {
+        let tz = aoc_timezone();
+        let at = |y, m, d, h| tz.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+        assert_eq!((2024, 1), next_unlock(at(2024, 11, 30, 23)));
+        assert_eq!((2024, 4), next_unlock(at(2024, 12, 3, 0)));
+        assert_eq!((2025, 1), next_unlock(at(2024, 12, 25, 1)));
+        assert_eq!((2025, 12), next_unlock(at(2025, 12, 11, 1)));
+        assert_eq!((2026, 1), next_unlock(at(2025, 12, 12, 1)));
+        // 04:00 UTC on the 4th is still the 3rd in AoC time
+        let utc = Utc.with_ymd_and_hms(2024, 12, 4, 4, 0, 0).unwrap();
+        assert_eq!((2024, 4), next_unlock(utc.with_timezone(&tz)));
+    }
+
+    #[test]
+    fn formats_remaining() {
+        assert_eq!("00h 01m 05s", format_remaining(TimeDelta::seconds(65)));
+        assert_eq!("2d 03h 00m 00s", format_remaining(TimeDelta::hours(51)));
+    }
+}
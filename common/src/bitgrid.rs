@@ -0,0 +1,282 @@
+use crate::geom::Direction;
+
+/// One bit per cell of a grid, e.g. for the cells visited so far, packed into `u64`s. Clearing
+/// only zeroes the words that were set since the last clear, so it stays cheap to reuse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    bits: Bits,
+    width: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: Bits::new(width * height),
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set(&mut self, pos: impl Into<(usize, usize)>) {
+        self.test_and_set(pos);
+    }
+
+    pub fn test(&self, pos: impl Into<(usize, usize)>) -> bool {
+        self.bits.test(self.bit(pos.into()))
+    }
+
+    /// Sets the cell, returning whether it was already set
+    pub fn test_and_set(&mut self, pos: impl Into<(usize, usize)>) -> bool {
+        let bit = self.bit(pos.into());
+        self.bits.test_and_set(bit)
+    }
+
+    /// How many cells are set
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The positions of the set cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits.ones().map(|i| (i % self.width, i / self.width))
+    }
+
+    fn bit(&self, (x, y): (usize, usize)) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+}
+
+/// Four bits per cell of a grid, one per direction, e.g. for the ways each cell has been walked
+/// through so far. Clears as cheaply as a `BitGrid`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirBitGrid {
+    bits: Bits,
+    width: usize,
+    height: usize,
+}
+
+impl DirBitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: Bits::new(width * height * 4),
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set(&mut self, pos: impl Into<(usize, usize)>, dir: Direction) {
+        self.test_and_set(pos, dir);
+    }
+
+    pub fn test(&self, pos: impl Into<(usize, usize)>, dir: Direction) -> bool {
+        self.bits.test(self.bit(pos.into(), dir))
+    }
+
+    /// Sets the cell's bit for `dir`, returning whether it was already set
+    pub fn test_and_set(&mut self, pos: impl Into<(usize, usize)>, dir: Direction) -> bool {
+        let bit = self.bit(pos.into(), dir);
+        self.bits.test_and_set(bit)
+    }
+
+    /// How many cell & direction pairs are set
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The set cell & direction pairs, row by row & clockwise from up within a cell
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), Direction)> + '_ {
+        self.bits.ones().map(|i| {
+            let cell = i / 4;
+            (
+                (cell % self.width, cell / self.width),
+                Direction::ALL[i % 4],
+            )
+        })
+    }
+
+    fn bit(&self, (x, y): (usize, usize), dir: Direction) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        (y * self.width + x) * 4 + dir as usize
+    }
+}
+
+/// Bits packed into words, remembering which words have been touched since the last clear
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits {
+    words: Vec<u64>,
+    /// Each word that went from zero to non-zero since the last clear, once
+    dirty: Vec<usize>,
+}
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            dirty: vec![],
+        }
+    }
+
+    fn test(&self, bit: usize) -> bool {
+        self.words[bit / 64] & (1 << (bit % 64)) != 0
+    }
+
+    fn test_and_set(&mut self, bit: usize) -> bool {
+        let word = &mut self.words[bit / 64];
+        let mask = 1 << (bit % 64);
+        if *word & mask != 0 {
+            return true;
+        }
+        if *word == 0 {
+            self.dirty.push(bit / 64);
+        }
+        *word |= mask;
+        false
+    }
+
+    fn count(&self) -> usize {
+        self.dirty
+            .iter()
+            .map(|&i| self.words[i].count_ones() as usize)
+            .sum()
+    }
+
+    fn clear(&mut self) {
+        for i in self.dirty.drain(..) {
+            self.words[i] = 0;
+        }
+    }
+
+    /// The set bits in ascending order
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitGrid, DirBitGrid};
+    use crate::geom::{Direction, Point};
+
+    #[test]
+    fn sets_tests_and_clears_cells() {
+        let mut grid = BitGrid::new(13, 11);
+        assert!(!grid.test_and_set((3, 4)));
+        assert!(grid.test_and_set((3, 4)));
+        grid.set(Point::new(12, 10));
+        grid.set((0, 0));
+        grid.set((12, 4));
+        assert!(grid.test((12, 10)));
+        assert!(!grid.test((4, 3)));
+        assert_eq!(4, grid.count());
+        assert_eq!(
+            vec![(0, 0), (3, 4), (12, 4), (12, 10)],
+            grid.iter().collect::<Vec<_>>()
+        );
+
+        grid.clear();
+        assert_eq!(0, grid.count());
+        assert_eq!(None, grid.iter().next());
+        assert!(!grid.test_and_set((3, 4)));
+        assert_eq!(grid.count(), grid.iter().count());
+    }
+
+    #[test]
+    fn matches_a_grid_of_bools() {
+        let (width, height) = (17, 9);
+        let mut grid = BitGrid::new(width, height);
+        let mut bools = vec![false; width * height];
+        // a fixed pseudo-random walk over the cells, clearing now & again
+        let mut seed = 7u64;
+        for step in 0..2_000 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let i = (seed >> 33) as usize % bools.len();
+            if step % 300 == 299 {
+                grid.clear();
+                bools.fill(false);
+            }
+            assert_eq!(bools[i], grid.test_and_set((i % width, i / width)));
+            bools[i] = true;
+            assert_eq!(bools.iter().filter(|&&b| b).count(), grid.count());
+        }
+        let set = (0..bools.len())
+            .filter(|&i| bools[i])
+            .map(|i| (i % width, i / width))
+            .collect::<Vec<_>>();
+        assert_eq!(set, grid.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn keeps_directions_apart() {
+        let mut grid = DirBitGrid::new(5, 5);
+        assert!(!grid.test_and_set((4, 4), Direction::Left));
+        assert!(!grid.test_and_set((4, 4), Direction::Up));
+        assert!(grid.test_and_set((4, 4), Direction::Left));
+        assert!(!grid.test((4, 4), Direction::Right));
+        assert!(!grid.test((3, 4), Direction::Left));
+        grid.set(Point::new(0, 1), Direction::Down);
+        assert_eq!(3, grid.count());
+        assert_eq!(
+            vec![
+                ((0, 1), Direction::Down),
+                ((4, 4), Direction::Up),
+                ((4, 4), Direction::Left)
+            ],
+            grid.iter().collect::<Vec<_>>()
+        );
+
+        grid.clear();
+        assert_eq!(0, grid.count());
+        assert!(!grid.test((4, 4), Direction::Up));
+    }
+
+    #[test]
+    #[should_panic(expected = "(5, 0) is outside a 5x5 grid")]
+    fn panics_outside_the_grid() {
+        BitGrid::new(5, 5).set((5, 0));
+    }
+}
@@ -0,0 +1,298 @@
+/// Every unordered pair of distinct items, `(&items[i], &items[j])` for `i < j` in order
+pub fn pairs<T>(items: &[T]) -> Pairs<'_, T> {
+    let n = items.len();
+    Pairs {
+        items,
+        a: 0,
+        b: 1,
+        remaining: n * n.saturating_sub(1) / 2,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pairs<'a, T> {
+    items: &'a [T],
+    a: usize,
+    b: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.b == self.items.len() {
+            self.a += 1;
+            self.b = self.a + 1;
+        }
+
+        let pair = (&self.items[self.a], &self.items[self.b]);
+        self.b += 1;
+        self.remaining -= 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Pairs<'_, T> {}
+
+/// Every way of picking `K` distinct items, keeping them in slice order, in lexicographic order
+/// of their positions
+pub fn combinations<T, const K: usize>(items: &[T]) -> Combinations<'_, T, K> {
+    Combinations {
+        items,
+        indices: std::array::from_fn(|i| i),
+        done: K > items.len(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T, const K: usize> {
+    items: &'a [T],
+    indices: [usize; K],
+    done: bool,
+}
+
+impl<'a, T, const K: usize> Iterator for Combinations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let picked = self.indices.map(|i| &self.items[i]);
+
+        // the rightmost index that can still move right, with the ones after it packed behind it
+        let n = self.items.len();
+        match (0..K).rev().find(|&i| self.indices[i] < n - K + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..K {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(picked)
+    }
+}
+
+/// Every way of choosing one of `options` for each of `len` slots, the last slot changing fastest.
+/// Handy for trying every operator between a line of numbers
+pub fn product<T>(options: &[T], len: usize) -> Product<'_, T> {
+    Product {
+        options,
+        indices: vec![0; len],
+        done: options.is_empty() && len > 0,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Product<'a, T> {
+    options: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T> Iterator for Product<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let chosen = self.indices.iter().map(|&i| &self.options[i]).collect();
+
+        // counts up in base `options.len()`, finishing when every slot wraps round
+        self.done = true;
+        for i in self.indices.iter_mut().rev() {
+            *i += 1;
+            if *i < self.options.len() {
+                self.done = false;
+                break;
+            }
+            *i = 0;
+        }
+
+        Some(chosen)
+    }
+}
+
+/// Every ordering of `items` by position, in lexicographic order of the positions. Items that
+/// are equal still give separate orderings, use `next_permutation` to skip those
+pub fn permutations<T>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: (0..items.len()).collect(),
+        done: false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let ordered = self.indices.iter().map(|&i| &self.items[i]).collect();
+        self.done = !next_permutation(&mut self.indices);
+        Some(ordered)
+    }
+}
+
+/// Rearranges `items` into the next larger ordering in lexicographic order, returning whether
+/// there was one. After the largest it wraps round to the smallest, i.e. sorted, & returns `false`
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // the longest non-increasing tail can't get any larger by itself
+    let Some(pivot) = (1..items.len())
+        .rev()
+        .find(|&i| items[i - 1] < items[i])
+        .map(|i| i - 1)
+    else {
+        items.reverse();
+        return false;
+    };
+
+    let successor = (pivot + 1..items.len())
+        .rev()
+        .find(|&i| items[i] > items[pivot])
+        .expect("the item after the pivot is larger");
+    items.swap(pivot, successor);
+    items[pivot + 1..].reverse();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{combinations, next_permutation, pairs, permutations, product};
+
+    #[test]
+    fn pairs_every_two_items_once() {
+        for n in 0..8 {
+            let items = (0..n).collect::<Vec<usize>>();
+            let mut expected = vec![];
+            for a in 0..n {
+                for b in a + 1..n {
+                    expected.push((a, b));
+                }
+            }
+
+            let mut iter = pairs(&items);
+            let mut actual = vec![];
+            assert_eq!(expected.len(), iter.len());
+            while let Some((&a, &b)) = iter.next() {
+                actual.push((a, b));
+                assert_eq!(expected.len() - actual.len(), iter.len());
+            }
+            assert_eq!(expected, actual);
+            assert_eq!(None, iter.next());
+        }
+    }
+
+    /// The positions of every `k` of `n` items by brute force, in lexicographic order
+    fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+        let mut subsets = (0u32..1 << n)
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| (0..n).filter(|i| mask & (1 << i) != 0).collect())
+            .collect::<Vec<Vec<usize>>>();
+        subsets.sort();
+        subsets
+    }
+
+    fn check_combinations<const K: usize>(n: usize) {
+        let items = (0..n).collect::<Vec<usize>>();
+        let picked = combinations::<_, K>(&items)
+            .map(|c| c.map(|&i| i).to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(subsets(n, K), picked, "{K} of {n}");
+    }
+
+    #[test]
+    fn combinations_match_subsets() {
+        for n in 0..8 {
+            check_combinations::<0>(n);
+            check_combinations::<1>(n);
+            check_combinations::<2>(n);
+            check_combinations::<3>(n);
+            check_combinations::<5>(n);
+        }
+    }
+
+    #[test]
+    fn products_count_in_the_base_of_the_options() {
+        for options in 0..4usize {
+            let ops = (0..options).collect::<Vec<_>>();
+            for len in 0..5u32 {
+                let expected = (0..options.pow(len))
+                    .map(|mut n| {
+                        let mut digits = vec![0; len as usize];
+                        for d in digits.iter_mut().rev() {
+                            *d = n % options;
+                            n /= options;
+                        }
+                        digits
+                    })
+                    .collect::<Vec<_>>();
+                let actual = product(&ops, len as usize)
+                    .map(|chosen| chosen.into_iter().copied().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                assert_eq!(expected, actual, "{options} options, {len} long");
+            }
+        }
+    }
+
+    #[test]
+    fn permutes_in_lexicographic_order() {
+        for n in 0..7 {
+            let items = (0..n).collect::<Vec<usize>>();
+            let all = permutations(&items)
+                .map(|p| p.into_iter().copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            assert_eq!((1..=n).product::<usize>(), all.len());
+            assert!(all.windows(2).all(|w| w[0] < w[1]));
+            assert!(all
+                .iter()
+                .all(|p| p.iter().collect::<BTreeSet<_>>().len() == n));
+        }
+    }
+
+    #[test]
+    fn next_permutation_skips_equal_orderings() {
+        for items in [
+            vec![],
+            vec![1],
+            vec![1, 1, 2],
+            vec![1, 2, 2, 3, 3],
+            vec![2, 2, 2],
+        ] {
+            let expected = permutations(&items)
+                .map(|p| p.into_iter().copied().collect::<Vec<_>>())
+                .collect::<BTreeSet<_>>();
+
+            let mut current = items.clone();
+            let mut actual = vec![current.clone()];
+            while next_permutation(&mut current) {
+                actual.push(current.clone());
+            }
+            assert_eq!(items, current, "wraps round to sorted");
+            assert_eq!(expected.into_iter().collect::<Vec<_>>(), actual);
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Project settings, read from the nearest `aoc.toml` at or above the working directory
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub year: i32,
+    pub crate_name: String,
+    /// Where real inputs are cached, `AOC_CACHE` takes precedence when set
+    pub cache_dir: Option<PathBuf>,
+    /// Identifies the project to adventofcode.com in the user agent
+    pub repository: Option<String>,
+    /// An email or handle adventofcode.com can use to reach whoever runs the tool
+    pub contact: Option<String>,
+    #[serde(default)]
+    pub paths: Paths,
+    #[serde(default)]
+    pub fetch: Fetch,
+    /// Named AoC accounts, each with their own session & inputs
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// The profile selected with [`Config::with_profile`], if any
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+    /// The directory containing the config file, which all paths are relative to
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Paths {
+    pub days: PathBuf,
+    pub bins: PathBuf,
+    pub test_inputs: PathBuf,
+    /// Scaffolding templates overriding the built-in ones
+    pub templates: PathBuf,
+    /// Encrypted copies of the cache, safe to commit
+    pub vault: PathBuf,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            days: "src/days".into(),
+            bins: "src/bin".into(),
+            test_inputs: "test_input".into(),
+            templates: "templates".into(),
+            vault: "inputs.vault".into(),
+        }
+    }
+}
+
+/// How politely to make requests to adventofcode.com
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Fetch {
+    /// The minimum gap between two requests
+    pub min_interval_secs: u64,
+    /// How many times to retry a request that failed with a timeout or server error
+    pub max_retries: u32,
+}
+
+impl Default for Fetch {
+    fn default() -> Self {
+        Self {
+            min_interval_secs: 5,
+            max_retries: 3,
+        }
+    }
+}
+
+/// An AoC account, whose inputs & answers are cached in their own subdirectory of the cache dir
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct Profile {
+    /// The env var holding this account's session cookie, `AOC_SESSION_<NAME>` by default
+    pub session_env: Option<String>,
+    /// Defaults to the profile name
+    pub cache_subdir: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn discover() -> Result<Self> {
+        let cwd = std::env::current_dir().context("failed to find current dir")?;
+        Self::discover_from(&cwd)
+    }
+
+    pub fn discover_from(dir: &Path) -> Result<Self> {
+        let Some(root) = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(CONFIG_FILE).is_file())
+        else {
+            bail!("no {CONFIG_FILE} found in {dir:?} or any parent directory");
+        };
+
+        Self::load(root)
+    }
+
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(CONFIG_FILE);
+        let contents =
+            std::fs::read_to_string(&path).with_context(|| format!("failed to read {path:?}"))?;
+        let mut config: Config =
+            toml::from_str(&contents).with_context(|| format!("failed to parse {path:?}"))?;
+        config.root = root.to_path_buf();
+
+        Ok(config)
+    }
+
+    /// The outermost directory with an `aoc.toml`, which holds every year's crate
+    pub fn workspace_root(&self) -> &Path {
+        self.root
+            .ancestors()
+            .filter(|ancestor| ancestor.join(CONFIG_FILE).is_file())
+            .last()
+            .unwrap_or(&self.root)
+    }
+
+    /// The config of each year in the workspace, from its root & the directories directly inside it
+    pub fn years(&self) -> Result<Vec<Self>> {
+        let root = self.workspace_root();
+        let mut years = vec![Self::load(root)?];
+        let entries =
+            std::fs::read_dir(root).with_context(|| format!("failed to list {root:?}"))?;
+        for entry in entries {
+            let path = entry.context("failed to read workspace entry")?.path();
+            if path.join(CONFIG_FILE).is_file() {
+                years.push(Self::load(&path)?);
+            }
+        }
+        years.sort_unstable_by_key(|config| config.year);
+
+        if let Some(pair) = years.windows(2).find(|pair| pair[0].year == pair[1].year) {
+            bail!(
+                "{year} is configured in both {a:?} and {b:?}",
+                year = pair[0].year,
+                a = pair[0].root,
+                b = pair[1].root
+            );
+        }
+
+        Ok(years)
+    }
+
+    /// This config with `name` as the active profile
+    pub fn with_profile(&self, name: &str) -> Result<Self> {
+        if !self.profiles.contains_key(name) {
+            bail!(
+                "unknown profile {name:?}, expected one of {known:?} from {CONFIG_FILE}",
+                known = self.profiles.keys().collect::<Vec<_>>()
+            );
+        }
+
+        Ok(Self {
+            active_profile: Some(name.to_string()),
+            ..self.clone()
+        })
+    }
+
+    /// A config for each profile, in name order
+    pub fn all_profiles(&self) -> Result<Vec<Self>> {
+        if self.profiles.is_empty() {
+            bail!("no profiles in {CONFIG_FILE}");
+        }
+
+        self.profiles
+            .keys()
+            .map(|name| self.with_profile(name))
+            .collect()
+    }
+
+    fn profile(&self) -> Option<(&str, &Profile)> {
+        let name = self.active_profile.as_deref()?;
+        self.profiles.get(name).map(|profile| (name, profile))
+    }
+
+    pub fn days_dir(&self) -> PathBuf {
+        self.root.join(&self.paths.days)
+    }
+
+    pub fn bins_dir(&self) -> PathBuf {
+        self.root.join(&self.paths.bins)
+    }
+
+    pub fn test_inputs_dir(&self) -> PathBuf {
+        self.root.join(&self.paths.test_inputs)
+    }
+
+    pub fn templates_dir(&self) -> PathBuf {
+        self.root.join(&self.paths.templates)
+    }
+
+    /// The cache dir, or the active profile's subdirectory of it
+    pub fn cache_dir(&self) -> Result<PathBuf> {
+        let root = match (std::env::var_os("AOC_CACHE"), &self.cache_dir) {
+            (Some(dir), _) => PathBuf::from(dir),
+            (None, Some(dir)) => expand_home(dir),
+            (None, None) => bail!("no cache dir, set AOC_CACHE or cache_dir in {CONFIG_FILE}"),
+        };
+
+        Ok(self.profile_dir(root))
+    }
+
+    /// The vault directory shared by every profile
+    pub fn vault_root(&self) -> PathBuf {
+        self.root.join(&self.paths.vault)
+    }
+
+    /// The vault directory, or the active profile's subdirectory of it
+    pub fn vault_dir(&self) -> PathBuf {
+        self.profile_dir(self.vault_root())
+    }
+
+    fn profile_dir(&self, root: PathBuf) -> PathBuf {
+        match self.profile() {
+            Some((name, profile)) => {
+                root.join(profile.cache_subdir.as_deref().unwrap_or(Path::new(name)))
+            }
+            None => root,
+        }
+    }
+
+    /// The env var holding the session cookie for the active profile
+    pub fn session_env(&self) -> String {
+        match self.profile() {
+            Some((
+                _,
+                Profile {
+                    session_env: Some(var),
+                    ..
+                },
+            )) => var.clone(),
+            Some((name, _)) => format!(
+                "AOC_SESSION_{name}",
+                name = name.to_uppercase().replace('-', "_")
+            ),
+            None => "AOC_SESSION".to_string(),
+        }
+    }
+
+    pub fn session(&self) -> Result<String> {
+        let var = self.session_env();
+        std::env::var(&var).with_context(|| format!("expected {var} env var"))
+    }
+
+    pub fn user_agent(&self) -> String {
+        let project = self.repository.as_deref().unwrap_or(&self.crate_name);
+        match &self.contact {
+            Some(contact) => format!("{project} by {contact}"),
+            None => project.to_string(),
+        }
+    }
+
+    pub fn input_file_name(&self, day: &str) -> String {
+        format!("{year}_{day}.txt", year = self.year)
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Config, Paths, Profile, CONFIG_FILE};
+
+    #[test]
+    fn discovers_from_subdirectory() {
+        let root = std::env::temp_dir().join("aoc-config-test-discover");
+        let nested = root.join("src/days");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            root.join(CONFIG_FILE),
+            "year = 2023\ncrate_name = \"advent_of_code_2023\"\n\n[paths]\ndays = \"solutions\"\n",
+        )
+        .unwrap();
+
+        let config = Config::discover_from(&nested).unwrap();
+        assert_eq!(2023, config.year);
+        assert_eq!(root, config.root);
+        assert_eq!(root.join("solutions"), config.days_dir());
+        assert_eq!(root.join("test_input"), config.test_inputs_dir());
+        assert_eq!("advent_of_code_2023", config.user_agent());
+    }
+
+    #[test]
+    fn finds_every_year_in_the_workspace() {
+        let root = std::env::temp_dir().join("aoc-config-test-years");
+        let _ = std::fs::remove_dir_all(&root);
+        for (dir, year) in [("", 2024), ("aoc-2023", 2023), ("aoc-2025", 2025)] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(
+                root.join(dir).join(CONFIG_FILE),
+                format!("year = {year}\ncrate_name = \"advent_of_code_{year}\"\n"),
+            )
+            .unwrap();
+        }
+        std::fs::create_dir_all(root.join("common/src")).unwrap();
+
+        let config = Config::discover_from(&root.join("aoc-2025")).unwrap();
+        assert_eq!(2025, config.year);
+        assert_eq!(root, config.workspace_root());
+        let years = config.years().unwrap();
+        assert_eq!(
+            vec![
+                (2023, root.join("aoc-2023")),
+                (2024, root.clone()),
+                (2025, root.join("aoc-2025"))
+            ],
+            years
+                .into_iter()
+                .map(|config| (config.year, config.root))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn profiles_get_their_own_cache_and_session() {
+        let mut config: Config = toml::from_str(
+            "year = 2024
+crate_name = \"advent_of_code_2024\"
+cache_dir = \"/cache\"
+
+[profiles.personal]
+
+[profiles.work-laptop]
+session_env = \"WORK_SESSION\"
+cache_subdir = \"work\"
+",
+        )
+        .unwrap();
+        config.root = PathBuf::from("/repo");
+        assert_eq!(Profile::default(), config.profiles["personal"]);
+
+        let root = config.cache_dir().unwrap();
+        assert_eq!(
+            root.join("personal"),
+            config
+                .with_profile("personal")
+                .unwrap()
+                .cache_dir()
+                .unwrap()
+        );
+        assert_eq!("AOC_SESSION", config.session_env());
+        assert_eq!(
+            "AOC_SESSION_PERSONAL",
+            config.with_profile("personal").unwrap().session_env()
+        );
+
+        let all = config.all_profiles().unwrap();
+        assert_eq!(
+            vec![Some("personal"), Some("work-laptop")],
+            all.iter()
+                .map(|c| c.active_profile.as_deref())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("WORK_SESSION", all[1].session_env());
+        assert_eq!(root.join("work"), all[1].cache_dir().unwrap());
+        assert_eq!(PathBuf::from("/repo/inputs.vault/work"), all[1].vault_dir());
+        assert_eq!(PathBuf::from("/repo/inputs.vault"), all[1].vault_root());
+        assert!(config.with_profile("missing").is_err());
+    }
+
+    #[test]
+    fn parses_repository_config() {
+        let common = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let config = Config::discover_from(&common).unwrap();
+        assert_eq!(common.parent().unwrap(), config.root);
+        assert_eq!(2024, config.year);
+        assert_eq!("advent_of_code_2024", config.crate_name);
+        assert_eq!(Paths::default(), config.paths);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+    iter::Sum,
+};
+
+/// A multiset counting how many times each key has been added. Keys that were never added count
+/// 0. `S` picks the hasher, so hot loops can swap in a faster one than the default
+#[derive(Debug, Clone)]
+pub struct Counter<K, S = RandomState> {
+    counts: HashMap<K, u64, S>,
+}
+
+impl<K, S: Default> Default for Counter<K, S> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher + Default> Counter<K, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Counter<K, S> {
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    pub fn add_n(&mut self, key: K, n: u64) {
+        if n > 0 {
+            *self.counts.entry(key).or_default() += n;
+        }
+    }
+
+    pub fn get(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or_default()
+    }
+
+    /// How many keys have been added altogether, counting repeats
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// How many different keys have been added
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Every key with its count, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> + '_ {
+        self.counts.iter().map(|(key, &n)| (key, n))
+    }
+
+    /// The `k` keys with the highest counts, highest first & ties broken by the smaller key
+    pub fn most_common(&self, k: usize) -> Vec<(&K, u64)>
+    where
+        K: Ord,
+    {
+        let mut common = self.iter().collect::<Vec<_>>();
+        common.sort_unstable_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.cmp(b)));
+        common.truncate(k);
+        common
+    }
+
+    /// Adds every count from `other` to this counter
+    pub fn merge(&mut self, other: Self) {
+        for (key, n) in other.counts {
+            self.add_n(key, n);
+        }
+    }
+
+    /// Moves every key into `next` through `f`, which is given each key with its count to add
+    /// whatever keys it becomes, then swaps the two. This counter ends up holding the next
+    /// generation & `next` ends up empty, so both allocations are reused generation to generation
+    pub fn drain_into(&mut self, next: &mut Self, mut f: impl FnMut(K, u64, &mut Self)) {
+        for (key, n) in self.counts.drain() {
+            f(key, n, next);
+        }
+        std::mem::swap(self, next);
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher + Default> FromIterator<K> for Counter<K, S> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Extend<K> for Counter<K, S> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher + Default> Sum for Counter<K, S> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(), |mut total, counter| {
+            total.merge(counter);
+            total
+        })
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> PartialEq for Counter<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Eq for Counter<K, S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn counts_what_was_added() {
+        let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(5, counter.get(&'a'));
+        assert_eq!(0, counter.get(&'z'));
+        assert_eq!(11, counter.total());
+        assert_eq!(5, counter.len());
+
+        counter.add_n('z', 3);
+        counter.add_n('y', 0);
+        counter.extend("zz".chars());
+        assert_eq!(5, counter.get(&'z'));
+        assert_eq!(0, counter.get(&'y'));
+        assert_eq!(6, counter.len());
+        assert_eq!(
+            vec![(&'a', 5), (&'z', 5), (&'b', 2)],
+            counter.most_common(3)
+        );
+        assert_eq!(6, counter.most_common(10).len());
+    }
+
+    #[test]
+    fn merges_and_sums() {
+        let mut a = [1, 1, 2].into_iter().collect::<Counter<u32>>();
+        let b = [2, 3].into_iter().collect::<Counter<u32>>();
+        let total = [a.clone(), b.clone()].into_iter().sum::<Counter<u32>>();
+        a.merge(b);
+        assert_eq!(a, total);
+        assert_eq!(vec![(&1, 2), (&2, 2), (&3, 1)], total.most_common(3));
+        assert!(Vec::<Counter<u32>>::new()
+            .into_iter()
+            .sum::<Counter<u32>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn drains_into_the_next_generation() {
+        // each n splits into n / 2 & n - n / 2 until it's 1
+        let mut counter = [8u64, 5].into_iter().collect::<Counter<_>>();
+        let mut next = Counter::new();
+        for _ in 0..3 {
+            counter.drain_into(&mut next, |n, count, next| {
+                if n == 1 {
+                    next.add_n(1, count);
+                } else {
+                    next.add_n(n / 2, count);
+                    next.add_n(n - n / 2, count);
+                }
+            });
+            assert!(next.is_empty());
+        }
+        assert_eq!(vec![(&1, 13)], counter.most_common(1));
+        assert_eq!(13, counter.total());
+    }
+}
@@ -0,0 +1,142 @@
+/// Decimal & other base digit tricks for unsigned integers, without going through strings
+pub trait Digits: Sized + Copy {
+    /// How many decimal digits it's written with, 0 being written with one
+    fn digit_count(self) -> u32;
+
+    /// `10^exp`, or `None` if that doesn't fit
+    fn pow10(exp: u32) -> Option<Self>;
+
+    /// The number written as `self` followed by `right`, e.g. `12` & `345` make `12345`. `None` if
+    /// that doesn't fit
+    fn checked_concat(self, right: Self) -> Option<Self>;
+
+    /// Splits off the lowest `low_digits` decimal digits, e.g. `12345` at 2 is `(123, 45)`.
+    /// Splitting off more digits than there are leaves `0` on the left
+    fn split_at_digit(self, low_digits: u32) -> (Self, Self);
+
+    /// The digits in `base`, least significant first. 0 has the single digit 0
+    fn digits(self, base: Self) -> DigitIter<Self>;
+
+    /// The number written with its digits in `base` the other way round, e.g. `1230` is `321` in
+    /// base 10. `None` if that doesn't fit
+    fn reverse_digits(self, base: Self) -> Option<Self>;
+}
+
+/// The digits of a number from least to most significant, see `Digits::digits`
+#[derive(Debug, Clone)]
+pub struct DigitIter<T> {
+    rest: Option<T>,
+    base: T,
+}
+
+macro_rules! impl_digits {
+    ($($ty:ty),*) => {
+        $(
+            impl Digits for $ty {
+                fn digit_count(self) -> u32 {
+                    self.checked_ilog10().unwrap_or(0) + 1
+                }
+
+                fn pow10(exp: u32) -> Option<Self> {
+                    <$ty>::checked_pow(10, exp)
+                }
+
+                fn checked_concat(self, right: Self) -> Option<Self> {
+                    self.checked_mul(Self::pow10(right.digit_count())?)?
+                        .checked_add(right)
+                }
+
+                fn split_at_digit(self, low_digits: u32) -> (Self, Self) {
+                    match Self::pow10(low_digits) {
+                        Some(pow) => (self / pow, self % pow),
+                        None => (0, self),
+                    }
+                }
+
+                fn digits(self, base: Self) -> DigitIter<Self> {
+                    assert!(base >= 2, "base {base} has no digits");
+                    DigitIter {
+                        rest: Some(self),
+                        base,
+                    }
+                }
+
+                fn reverse_digits(self, base: Self) -> Option<Self> {
+                    self.digits(base)
+                        .try_fold(0, |n: $ty, d| n.checked_mul(base)?.checked_add(d))
+                }
+            }
+
+            impl Iterator for DigitIter<$ty> {
+                type Item = $ty;
+
+                fn next(&mut self) -> Option<$ty> {
+                    let n = self.rest?;
+                    self.rest = (n >= self.base).then(|| n / self.base);
+                    Some(n % self.base)
+                }
+            }
+        )*
+    };
+}
+
+impl_digits!(u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::Digits;
+
+    #[test]
+    fn agrees_with_formatting() {
+        for n in (0..100_000u32).chain([u32::MAX - 1, u32::MAX]) {
+            let s = n.to_string();
+            assert_eq!(s.len() as u32, n.digit_count(), "{n}");
+
+            let digits = n.digits(10).map(|d| d.to_string()).collect::<String>();
+            assert_eq!(s.chars().rev().collect::<String>(), digits);
+            assert_eq!(
+                u32::try_from(digits.parse::<u64>().unwrap()).ok(),
+                n.reverse_digits(10)
+            );
+
+            for at in 0..=s.len() {
+                let (high, low) = s.split_at(s.len() - at);
+                let expected = (high.parse().unwrap_or(0), low.parse().unwrap_or(0));
+                assert_eq!(expected, n.split_at_digit(at as u32), "{n} at {at}");
+            }
+        }
+        assert_eq!((0, u32::MAX), u32::MAX.split_at_digit(10));
+        assert_eq!((0, u32::MAX), u32::MAX.split_at_digit(11));
+    }
+
+    #[test]
+    fn concatenates_until_overflow() {
+        assert_eq!(Some(12_345), 12u32.checked_concat(345));
+        assert_eq!(Some(10), 1u32.checked_concat(0));
+        assert_eq!(
+            Some(1_000),
+            10u64.checked_concat(0).unwrap().checked_concat(0)
+        );
+        assert_eq!(Some(u32::MAX), 429_496_729u32.checked_concat(5));
+        assert_eq!(None, 429_496_729u32.checked_concat(6));
+        assert_eq!(None, 1u64.checked_concat(u64::MAX));
+        assert_eq!(
+            Some(u128::MAX),
+            (u128::MAX / 10).checked_concat(u128::MAX % 10)
+        );
+    }
+
+    #[test]
+    fn works_in_any_base() {
+        assert_eq!(vec![1, 0, 1, 1], 13u64.digits(2).collect::<Vec<_>>());
+        assert_eq!(vec![0], 0u64.digits(16).collect::<Vec<_>>());
+        assert_eq!(vec![15, 15], 255u128.digits(16).collect::<Vec<_>>());
+        assert_eq!(Some(11), 13u64.reverse_digits(2));
+        assert_eq!(Some(1), 1_000u128.reverse_digits(10));
+        assert_eq!(Some(u64::MAX), u64::MAX.reverse_digits(2));
+        assert_eq!(None, 4_000_000_009u32.reverse_digits(10));
+        assert_eq!(Some(100_000_000_000_000_000_000u128), u128::pow10(20));
+        assert_eq!(None, u64::pow10(20));
+        assert_eq!(Some(10_000_000_000_000_000_000u64), u64::pow10(19));
+    }
+}
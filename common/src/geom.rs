@@ -0,0 +1,371 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// A position on a grid, with `y` growing downwards like the lines of an input
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The offset between two points
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Add<Output = T>> Point<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when diagonal steps count as one, like a king's moves
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + Ord + Default + Sub<Output = T> + Add<Output = T>> Vector<T> {
+    pub fn manhattan_len(self) -> T {
+        Point::default().manhattan(Point::new(self.x, self.y))
+    }
+
+    pub fn chebyshev_len(self) -> T {
+        Point::default().chebyshev(Point::new(self.x, self.y))
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Vector<T> {
+    /// A quarter turn clockwise on screen, so up becomes right
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise on screen, so up becomes left
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl Point<usize> {
+    /// Moves by `v`, or `None` if either coordinate would go below zero
+    pub fn checked_add(self, v: Vector<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(v.x)?,
+            self.y.checked_add_signed(v.y)?,
+        ))
+    }
+
+    /// Moves by `v`, or `None` if that leaves a `width` by `height` rectangle at the origin
+    pub fn checked_add_within(self, v: Vector<isize>, width: usize, height: usize) -> Option<Self> {
+        self.checked_add(v).filter(|p| p.x < width && p.y < height)
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vector<T>) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector<T>) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Vector<T> {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vector<T>> for Point<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vector<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vector<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Vector<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// One of the four orthogonal directions on screen, `Up` being towards the first line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn vector(self) -> Vector<isize> {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::Down => Vector::new(0, 1),
+            Self::Left => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the eight orthogonal & diagonal directions on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn vector(self) -> Vector<isize> {
+        match self {
+            Self::Up => Vector::new(0, -1),
+            Self::UpRight => Vector::new(1, -1),
+            Self::Right => Vector::new(1, 0),
+            Self::DownRight => Vector::new(1, 1),
+            Self::Down => Vector::new(0, 1),
+            Self::DownLeft => Vector::new(-1, 1),
+            Self::Left => Vector::new(-1, 0),
+            Self::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// An eighth turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Direction8, Point, Vector};
+
+    #[test]
+    fn combines_points_and_vectors() {
+        let a = Point::new(3, 4);
+        let b = Point::new(1, 7);
+        let v = a - b;
+        assert_eq!(Vector::new(2, -3), v);
+        assert_eq!(a, b + v);
+        assert_eq!(b, a - v);
+        assert_eq!(Vector::new(4, -6), v * 2);
+        assert_eq!(v, Vector::new(4, -6) / 2);
+        assert_eq!(Vector::new(-2, 3), -v);
+        assert_eq!(Vector::new(0, 0), v + -v);
+
+        let mut c = b;
+        c += v;
+        assert_eq!(a, c);
+        c -= v;
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1usize, 5);
+        let b = Point::new(4usize, 1);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(7, b.manhattan(a));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(0, a.manhattan(a));
+
+        let v = Vector::new(-3, 4);
+        assert_eq!(7, v.manhattan_len());
+        assert_eq!(4, v.chebyshev_len());
+    }
+
+    #[test]
+    fn adds_only_into_bounds() {
+        let p = Point::new(1usize, 0);
+        assert_eq!(Some(Point::new(0, 0)), p.checked_add(Vector::new(-1, 0)));
+        assert_eq!(None, p.checked_add(Vector::new(0, -1)));
+        assert_eq!(
+            Some(Point::new(2, 1)),
+            p.checked_add_within(Vector::new(1, 1), 3, 2)
+        );
+        assert_eq!(None, p.checked_add_within(Vector::new(2, 0), 3, 2));
+        assert_eq!(None, p.checked_add_within(Vector::new(0, 2), 3, 2));
+    }
+
+    #[test]
+    fn turns_agree_with_vectors() {
+        assert_eq!(Vector::new(0, -1), Direction::Up.vector());
+        for dir in Direction::ALL {
+            assert_eq!(dir.vector().rotate_right(), dir.turn_right().vector());
+            assert_eq!(dir.vector().rotate_left(), dir.turn_left().vector());
+            assert_eq!(-dir.vector(), dir.opposite().vector());
+            assert_eq!(dir, dir.turn_right().turn_left());
+            assert_eq!(dir.opposite(), dir.turn_right().turn_right());
+            assert_eq!(1, dir.vector().manhattan_len());
+            assert_eq!(dir.vector(), Direction8::from(dir).vector());
+        }
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+
+        for dir in Direction8::ALL {
+            assert_eq!(dir, dir.turn_right().turn_left());
+            assert_eq!(-dir.vector(), dir.opposite().vector());
+            assert_eq!(dir.opposite(), (0..4).fold(dir, |d, _| d.turn_right()));
+            assert_eq!(
+                dir.vector().rotate_right(),
+                dir.turn_right().turn_right().vector()
+            );
+            assert_eq!(dir.is_diagonal(), dir.vector().manhattan_len() == 2);
+            assert_eq!(1, dir.vector().chebyshev_len());
+        }
+    }
+}
@@ -0,0 +1,777 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::grid::Grid;
+
+/// The nodes one step away from a node. Closures returning an iterator of nodes implement it
+pub trait Neighbours<N> {
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = N>;
+}
+
+impl<N, F, I> Neighbours<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = N> {
+        self(node).into_iter()
+    }
+}
+
+/// Every cell of a grid is joined to its orthogonal neighbours
+impl<T> Neighbours<(usize, usize)> for Grid<T> {
+    fn neighbours(&self, node: &(usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours4(*node)
+    }
+}
+
+/// The nodes one step away from a node & the cost of each step. Closures returning an iterator of
+/// `(node, cost)` implement it
+pub trait Edges<N, C> {
+    fn edges(&self, node: &N) -> impl Iterator<Item = (N, C)>;
+}
+
+impl<N, C, F, I> Edges<N, C> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    fn edges(&self, node: &N) -> impl Iterator<Item = (N, C)> {
+        self(node).into_iter()
+    }
+}
+
+/// Visits every node reachable from `start` in breadth first order, with its distance from `start`
+pub fn bfs<N, G>(start: N, graph: &G) -> Bfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    Bfs {
+        graph,
+        seen: HashSet::from([start.clone()]),
+        queue: VecDeque::from([(start, 0)]),
+        limits: Limits::default(),
+    }
+}
+
+/// Visits every node reachable from `start` in depth first order, with its depth in the search
+pub fn dfs<N, G>(start: N, graph: &G) -> Dfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    Dfs {
+        graph,
+        seen: HashSet::new(),
+        stack: vec![(start, 0)],
+        limits: Limits::default(),
+    }
+}
+
+struct Limits {
+    max_depth: usize,
+    visits_left: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            visits_left: usize::MAX,
+        }
+    }
+}
+
+pub struct Bfs<'a, N, G> {
+    graph: &'a G,
+    seen: HashSet<N>,
+    queue: VecDeque<(N, usize)>,
+    limits: Limits,
+}
+
+impl<N, G> Bfs<'_, N, G> {
+    /// Stops exploring past nodes `depth` steps from the start
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.limits.max_depth = depth;
+        self
+    }
+
+    /// Stops after visiting `visits` nodes
+    pub fn max_visits(mut self, visits: usize) -> Self {
+        self.limits.visits_left = visits;
+        self
+    }
+}
+
+impl<N, G> Iterator for Bfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.limits.visits_left == 0 {
+            return None;
+        }
+        let (node, depth) = self.queue.pop_front()?;
+        self.limits.visits_left -= 1;
+        if depth < self.limits.max_depth {
+            for next in self.graph.neighbours(&node) {
+                if self.seen.insert(next.clone()) {
+                    self.queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        Some((node, depth))
+    }
+}
+
+pub struct Dfs<'a, N, G> {
+    graph: &'a G,
+    seen: HashSet<N>,
+    stack: Vec<(N, usize)>,
+    limits: Limits,
+}
+
+impl<N, G> Dfs<'_, N, G> {
+    /// Stops exploring past nodes `depth` steps deep in the search
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.limits.max_depth = depth;
+        self
+    }
+
+    /// Stops after visiting `visits` nodes
+    pub fn max_visits(mut self, visits: usize) -> Self {
+        self.limits.visits_left = visits;
+        self
+    }
+}
+
+impl<N, G> Iterator for Dfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.limits.visits_left == 0 {
+            return None;
+        }
+        loop {
+            let (node, depth) = self.stack.pop()?;
+            if !self.seen.insert(node.clone()) {
+                continue;
+            }
+            self.limits.visits_left -= 1;
+            if depth < self.limits.max_depth {
+                let start = self.stack.len();
+                self.stack.extend(
+                    self.graph
+                        .neighbours(&node)
+                        .filter(|next| !self.seen.contains(next))
+                        .map(|next| (next, depth + 1)),
+                );
+                // so neighbours are visited in the order they're given
+                self.stack[start..].reverse();
+            }
+
+            return Some((node, depth));
+        }
+    }
+}
+
+/// The cheapest path from `start` to a node matching `is_goal` & its cost, costs being non-negative
+pub fn dijkstra<N, C, G>(
+    start: N,
+    graph: &G,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Edges<N, C>,
+{
+    astar(start, graph, |_| C::default(), is_goal)
+}
+
+/// Dijkstra guided by `heuristic`, which must never overestimate the remaining cost to a goal and
+/// never drop by more than the cost of a step, or the path found may not be the cheapest
+pub fn astar<N, C, G>(
+    start: N,
+    graph: &G,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    G: Edges<N, C>,
+{
+    struct Visit<N, C> {
+        node: N,
+        cost: C,
+        parent: usize,
+    }
+
+    let zero = C::default();
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut visits = vec![Visit {
+        node: start.clone(),
+        cost: zero,
+        parent: usize::MAX,
+    }];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        if cost > visits[i].cost {
+            continue;
+        }
+        let node = visits[i].node.clone();
+        if is_goal(&node) {
+            let mut path = vec![];
+            let mut i = i;
+            while i != usize::MAX {
+                path.push(visits[i].node.clone());
+                i = visits[i].parent;
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+
+        for (next, step) in graph.edges(&node) {
+            let next_cost = cost + step;
+            let j = match index.get(&next) {
+                Some(&j) if next_cost >= visits[j].cost => continue,
+                Some(&j) => {
+                    visits[j].cost = next_cost;
+                    visits[j].parent = i;
+                    j
+                }
+                None => {
+                    index.insert(next.clone(), visits.len());
+                    visits.push(Visit {
+                        node: next.clone(),
+                        cost: next_cost,
+                        parent: i,
+                    });
+                    visits.len() - 1
+                }
+            };
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/// A cycle in a graph that was expected to have none, each node leading to the next & the last
+/// back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "found a cycle through {:?}", self.0)
+    }
+}
+
+impl<N: Debug> Error for Cycle<N> {}
+
+/// Why `count_paths` couldn't count the paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountPathsError<N> {
+    /// A cycle is reachable, so there are endless paths
+    Cycle(Cycle<N>),
+    /// There are more paths than fit in a `u64`
+    Overflow,
+}
+
+impl<N> From<Cycle<N>> for CountPathsError<N> {
+    fn from(cycle: Cycle<N>) -> Self {
+        CountPathsError::Cycle(cycle)
+    }
+}
+
+impl<N: Debug> Display for CountPathsError<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CountPathsError::Cycle(cycle) => write!(f, "{cycle}"),
+            CountPathsError::Overflow => write!(f, "too many paths to count in a u64"),
+        }
+    }
+}
+
+impl<N: Debug> Error for CountPathsError<N> {}
+
+/// Orders `nodes` & every node reachable from them so each comes before all of its neighbours
+pub fn topological_sort<N, G>(
+    nodes: impl IntoIterator<Item = N>,
+    graph: &G,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let indexed = Indexed::discover(nodes, graph);
+    match indexed.sort() {
+        Ok(order) => Ok(order
+            .into_iter()
+            .map(|i| indexed.nodes[i].clone())
+            .collect()),
+        Err(cycle) => Err(indexed.cycle(cycle)),
+    }
+}
+
+/// Groups `nodes` & every node reachable from them into weakly connected components, so two
+/// nodes share a component if there's a path between them ignoring the direction of edges
+pub fn connected_components<N, G>(nodes: impl IntoIterator<Item = N>, graph: &G) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let indexed = Indexed::discover(nodes, graph);
+    let mut undirected = vec![vec![]; indexed.nodes.len()];
+    for (i, edges) in indexed.edges.iter().enumerate() {
+        for &j in edges {
+            undirected[i].push(j);
+            undirected[j].push(i);
+        }
+    }
+
+    let mut component_of = vec![usize::MAX; indexed.nodes.len()];
+    let mut components = vec![];
+    for start in 0..indexed.nodes.len() {
+        if component_of[start] != usize::MAX {
+            continue;
+        }
+        let mut component = vec![];
+        component_of[start] = components.len();
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            component.push(indexed.nodes[i].clone());
+            for &j in &undirected[i] {
+                if component_of[j] == usize::MAX {
+                    component_of[j] = components.len();
+                    stack.push(j);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// The number of paths from `start` to nodes matching `is_goal`, which may continue past a goal to
+/// reach others. Fails if a cycle is reachable, which would allow endless paths, or if the count
+/// doesn't fit in a `u64`
+pub fn count_paths<N, G>(
+    start: N,
+    graph: &G,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Result<u64, CountPathsError<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let indexed = Indexed::discover([start], graph);
+    let order = indexed.sort().map_err(|cycle| indexed.cycle(cycle))?;
+
+    let mut paths = vec![0; indexed.nodes.len()];
+    for &i in order.iter().rev() {
+        paths[i] = indexed.edges[i]
+            .iter()
+            .try_fold(u64::from(is_goal(&indexed.nodes[i])), |total, &j| {
+                total.checked_add(paths[j])
+            })
+            .ok_or(CountPathsError::Overflow)?;
+    }
+
+    Ok(paths[0])
+}
+
+/// The part of a graph reachable from some nodes, with the nodes numbered in discovery order
+struct Indexed<N> {
+    nodes: Vec<N>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Indexed<N> {
+    fn discover(starts: impl IntoIterator<Item = N>, graph: &impl Neighbours<N>) -> Self {
+        let mut index = HashMap::new();
+        let mut nodes = vec![];
+        for start in starts {
+            if !index.contains_key(&start) {
+                index.insert(start.clone(), nodes.len());
+                nodes.push(start);
+            }
+        }
+
+        let mut edges = vec![];
+        while edges.len() < nodes.len() {
+            let node = nodes[edges.len()].clone();
+            let node_edges = graph
+                .neighbours(&node)
+                .map(|next| {
+                    *index.entry(next.clone()).or_insert_with(|| {
+                        nodes.push(next);
+                        nodes.len() - 1
+                    })
+                })
+                .collect();
+            edges.push(node_edges);
+        }
+
+        Self { nodes, edges }
+    }
+
+    /// Kahn's algorithm, failing with the nodes left on or downstream of a cycle
+    fn sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree = vec![0; self.nodes.len()];
+        for &j in self.edges.iter().flatten() {
+            in_degree[j] += 1;
+        }
+        let mut queue = (0..self.nodes.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for &j in &self.edges[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len() {
+            Ok(order)
+        } else {
+            Err((0..self.nodes.len())
+                .filter(|&i| in_degree[i] > 0)
+                .collect())
+        }
+    }
+
+    /// Finds a cycle among the nodes `sort` left over, each of which has a leftover predecessor,
+    /// so walking backwards through predecessors must eventually repeat a node
+    fn cycle(&self, left: Vec<usize>) -> Cycle<N> {
+        let is_left = {
+            let mut is_left = vec![false; self.nodes.len()];
+            left.iter().for_each(|&i| is_left[i] = true);
+            is_left
+        };
+        let mut predecessor = vec![usize::MAX; self.nodes.len()];
+        for (i, edges) in self.edges.iter().enumerate() {
+            for &j in edges {
+                if is_left[i] && is_left[j] {
+                    predecessor[j] = i;
+                }
+            }
+        }
+
+        let mut walked = vec![];
+        let mut position = vec![usize::MAX; self.nodes.len()];
+        let mut i = left[0];
+        while position[i] == usize::MAX {
+            position[i] = walked.len();
+            walked.push(i);
+            i = predecessor[i];
+        }
+        // walked backwards, so reversing puts each node before its successor
+        let cycle = walked[position[i]..].iter().rev();
+        Cycle(cycle.map(|&i| self.nodes[i].clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+    use super::{
+        astar, bfs, connected_components, count_paths, dfs, dijkstra, topological_sort,
+        CountPathsError, Cycle,
+    };
+    use crate::grid::Grid;
+
+    /// Up to 7 nodes numbered from 0, with edges costing 0 to 9
+    #[derive(Debug, Clone)]
+    struct SmallGraph {
+        edges: Vec<Vec<(usize, u32)>>,
+    }
+
+    impl Arbitrary for SmallGraph {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let n = usize::arbitrary(g) % 7 + 1;
+            let edges = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .filter_map(|j| {
+                            let joined = u8::arbitrary(g) % 3 == 0;
+                            let cost = u32::from(u8::arbitrary(g) % 10);
+                            joined.then_some((j, cost))
+                        })
+                        .collect()
+                })
+                .collect();
+            Self { edges }
+        }
+    }
+
+    impl SmallGraph {
+        fn len(&self) -> usize {
+            self.edges.len()
+        }
+
+        fn successors(&self) -> impl Fn(&usize) -> Vec<usize> + '_ {
+            |&i| self.edges[i].iter().map(|&(j, _)| j).collect()
+        }
+
+        fn weighted(&self) -> impl Fn(&usize) -> Vec<(usize, u32)> + '_ {
+            |&i| self.edges[i].clone()
+        }
+
+        fn cost(&self, i: usize, j: usize) -> Option<u32> {
+            self.edges[i]
+                .iter()
+                .find(|&&(k, _)| k == j)
+                .map(|&(_, c)| c)
+        }
+
+        fn path_cost(&self, path: &[usize]) -> Option<u32> {
+            path.windows(2).map(|w| self.cost(w[0], w[1])).sum()
+        }
+
+        /// Every path from `from` that doesn't repeat a node, by brute force
+        fn simple_paths(&self, from: usize) -> Vec<Vec<usize>> {
+            let mut paths = vec![];
+            let mut stack = vec![vec![from]];
+            while let Some(path) = stack.pop() {
+                let last = *path.last().unwrap();
+                for &(next, _) in &self.edges[last] {
+                    if !path.contains(&next) {
+                        let mut longer = path.clone();
+                        longer.push(next);
+                        stack.push(longer);
+                    }
+                }
+                paths.push(path);
+            }
+            paths
+        }
+
+        fn paths_to(&self, from: usize, to: usize) -> Vec<Vec<usize>> {
+            let mut paths = self.simple_paths(from);
+            paths.retain(|p| p.last() == Some(&to));
+            paths
+        }
+
+        fn hops(&self, from: usize, to: usize) -> Option<usize> {
+            self.paths_to(from, to).iter().map(|p| p.len() - 1).min()
+        }
+
+        fn cheapest(&self, from: usize, to: usize) -> Option<u32> {
+            let paths = self.paths_to(from, to);
+            paths.iter().filter_map(|p| self.path_cost(p)).min()
+        }
+
+        /// Whether a cycle can be reached from `from`
+        fn reaches_cycle(&self, from: usize) -> bool {
+            self.simple_paths(from).iter().any(|path| {
+                let last = *path.last().unwrap();
+                self.edges[last].iter().any(|(next, _)| path.contains(next))
+            })
+        }
+
+        fn is_cycle(&self, cycle: &[usize]) -> bool {
+            !cycle.is_empty()
+                && (0..cycle.len())
+                    .all(|k| self.cost(cycle[k], cycle[(k + 1) % cycle.len()]).is_some())
+        }
+    }
+
+    fn check(prop: fn(SmallGraph) -> bool) {
+        QuickCheck::new().tests(500).quickcheck(prop);
+    }
+
+    #[test]
+    fn bfs_finds_fewest_hops() {
+        check(|graph| {
+            let succ = graph.successors();
+            let visited = bfs(0, &succ).collect::<Vec<_>>();
+            let expected = (0..graph.len())
+                .filter_map(|i| graph.hops(0, i).map(|hops| (i, hops)))
+                .collect::<HashSet<_>>();
+            let within_one = bfs(0, &succ).max_depth(1).count();
+            visited.len() == expected.len()
+                && visited.iter().cloned().collect::<HashSet<_>>() == expected
+                && visited.windows(2).all(|w| w[0].1 <= w[1].1)
+                && within_one == expected.iter().filter(|(_, hops)| *hops <= 1).count()
+                && bfs(0, &succ).max_visits(2).count() == expected.len().min(2)
+        });
+    }
+
+    #[test]
+    fn dfs_visits_everything_reachable_once() {
+        check(|graph| {
+            let succ = graph.successors();
+            let visited = dfs(0, &succ).map(|(i, _)| i).collect::<Vec<_>>();
+            let expected = (0..graph.len())
+                .filter(|&i| graph.hops(0, i).is_some())
+                .collect::<HashSet<_>>();
+            let shallow = dfs(0, &succ).max_depth(0).collect::<Vec<_>>();
+            visited.len() == expected.len()
+                && visited.iter().cloned().collect::<HashSet<_>>() == expected
+                && shallow == vec![(0, 0)]
+                && dfs(0, &succ).max_visits(3).count() == expected.len().min(3)
+        });
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        check(|graph| {
+            let edges = graph.weighted();
+            (0..graph.len()).all(|goal| {
+                match (dijkstra(0, &edges, |&i| i == goal), graph.cheapest(0, goal)) {
+                    (None, None) => true,
+                    (Some((path, cost)), Some(cheapest)) => {
+                        path.first() == Some(&0)
+                            && path.last() == Some(&goal)
+                            && graph.path_cost(&path) == Some(cost)
+                            && cost == cheapest
+                    }
+                    _ => false,
+                }
+            })
+        });
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        check(|graph| {
+            let edges = graph.weighted();
+            (0..graph.len()).all(|goal| {
+                // admissible, as it's half the true remaining cost
+                let heuristic = |&i: &usize| graph.cheapest(i, goal).unwrap_or(0) / 2;
+                let found = astar(0, &edges, heuristic, |&i| i == goal);
+                found.as_ref().map(|(_, cost)| *cost) == graph.cheapest(0, goal)
+                    && found.is_none_or(|(path, cost)| graph.path_cost(&path) == Some(cost))
+            })
+        });
+    }
+
+    #[test]
+    fn topological_sort_orders_edges_or_finds_a_cycle() {
+        check(|graph| {
+            let succ = graph.successors();
+            let has_cycle = (0..graph.len()).any(|i| graph.reaches_cycle(i));
+            match topological_sort(0..graph.len(), &succ) {
+                Ok(order) => {
+                    let mut position = vec![usize::MAX; graph.len()];
+                    order.iter().enumerate().for_each(|(k, &i)| position[i] = k);
+                    !has_cycle
+                        && order.len() == graph.len()
+                        && position.iter().all(|&k| k != usize::MAX)
+                        && (0..graph.len())
+                            .all(|i| succ(&i).iter().all(|&j| position[i] < position[j]))
+                }
+                Err(Cycle(cycle)) => has_cycle && graph.is_cycle(&cycle),
+            }
+        });
+    }
+
+    #[test]
+    fn components_match_union_find() {
+        check(|graph| {
+            let mut parent = (0..graph.len()).collect::<Vec<_>>();
+            fn root(parent: &mut [usize], i: usize) -> usize {
+                if parent[i] == i {
+                    i
+                } else {
+                    let r = root(parent, parent[i]);
+                    parent[i] = r;
+                    r
+                }
+            }
+            for i in 0..graph.len() {
+                for &(j, _) in &graph.edges[i] {
+                    let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[ri] = rj;
+                }
+            }
+
+            let roots = (0..graph.len())
+                .map(|i| root(&mut parent, i))
+                .collect::<Vec<_>>();
+
+            let components = connected_components(0..graph.len(), &graph.successors());
+            let in_components = components.iter().map(Vec::len).sum::<usize>();
+            in_components == graph.len()
+                && components.iter().all(|component| {
+                    let r = roots[component[0]];
+                    component.iter().all(|&i| roots[i] == r)
+                        && roots.iter().filter(|&&root| root == r).count() == component.len()
+                })
+        });
+    }
+
+    #[test]
+    fn counts_paths_through_dags() {
+        check(|graph| {
+            let succ = graph.successors();
+            let reaches_cycle = graph.reaches_cycle(0);
+            (0..graph.len()).all(|goal| match count_paths(0, &succ, |&i| i == goal) {
+                Ok(count) => !reaches_cycle && count == graph.paths_to(0, goal).len() as u64,
+                Err(CountPathsError::Cycle(Cycle(cycle))) => {
+                    reaches_cycle && graph.is_cycle(&cycle)
+                }
+                Err(CountPathsError::Overflow) => false,
+            })
+        });
+    }
+
+    #[test]
+    fn fails_to_count_more_paths_than_fit() {
+        // every node steps one or two on, so the paths to `n` follow the fibonacci numbers
+        let steps = |n: u32| move |&i: &u32| (i + 1..=(i + 2).min(n)).collect::<Vec<_>>();
+        assert_eq!(
+            Ok(4_660_046_610_375_530_309),
+            count_paths(0, &steps(90), |&i| i == 90)
+        );
+        assert_eq!(
+            Err(CountPathsError::Overflow),
+            count_paths(0, &steps(100), |&i| i == 100)
+        );
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid = Grid::parse("...\n.#.\n...", |b| b == b'#').unwrap();
+        let open = |&pos: &(usize, usize)| grid.neighbours4(pos).filter(|&p| !grid[p]);
+        let far = bfs((0, 0), &open).last();
+        assert_eq!(Some(((2, 2), 4)), far);
+        assert_eq!(9, bfs((1, 1), &grid).count());
+
+        let step =
+            |&pos: &(usize, usize)| grid.neighbours4(pos).map(|p| (p, 1 + 4 * grid[p] as u32));
+        let (path, cost) = dijkstra((0, 1), &step, |&p| p == (2, 1)).unwrap();
+        assert_eq!((5, 4), (path.len(), cost));
+
+        let cycle = topological_sort([(0, 0)], &grid).unwrap_err();
+        assert_eq!(2, cycle.0.len());
+        assert!(cycle.to_string().starts_with("found a cycle through"));
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops::{Index, IndexMut};
+
+use anyhow::{bail, Result};
+
+use crate::geom::Point;
+
+/// Offsets to the orthogonal neighbours, clockwise from up
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the orthogonal & diagonal neighbours, clockwise from up
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangle of cells stored row by row, indexed by `(x, y)` with `y` growing downwards
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per byte & one row per line, with or without CRLFs & a trailing newline
+    pub fn parse(input: &str, mut cell: impl FnMut(u8) -> T) -> Result<Self> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in input.trim_end_matches(['\r', '\n']).split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            match width {
+                None => width = Some(line.len()),
+                Some(width) if width != line.len() => bail!(
+                    "line {line_no} is {len} wide, expected {width}",
+                    line_no = height + 1,
+                    len = line.len()
+                ),
+                Some(_) => {}
+            }
+            cells.extend(line.bytes().map(&mut cell));
+            height += 1;
+        }
+        let width = width.unwrap_or_default();
+
+        Ok(Self {
+            height: if width == 0 { 0 } else { height },
+            cells,
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Whether a signed position lies inside the grid
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y)
+    }
+
+    /// The position `(dx, dy)` away from `pos`, if that's still inside the grid
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.index_of(pos.0, pos.1).map(|_| pos)
+    }
+
+    /// The orthogonal neighbours of `pos` inside the grid
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The orthogonal & diagonal neighbours of `pos` inside the grid
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // `chunks_exact` panics on 0, which an empty grid has no rows for anyway
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell matching `predicate`, row by row
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Sets every cell back to `value`, keeping the allocation
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        let Some(i) = self.index_of(x, y) else {
+            panic!(
+                "({x}, {y}) is outside a {}x{} grid",
+                self.width, self.height
+            );
+        };
+        &self.cells[i]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let Some(i) = self.index_of(x, y) else {
+            panic!(
+                "({x}, {y}) is outside a {}x{} grid",
+                self.width, self.height
+            );
+        };
+        &mut self.cells[i]
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point<usize>) -> &T {
+        &self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<usize>) -> &mut T {
+        &mut self[(p.x, p.y)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::geom::Point;
+
+    #[test]
+    fn parses_any_line_endings() {
+        let grid = Grid::parse("ab\ncd\n", |b| b).unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        for input in ["ab\ncd", "ab\r\ncd\r\n", "ab\r\ncd", "ab\ncd\n\n"] {
+            assert_eq!(grid, Grid::parse(input, |b| b).unwrap(), "{input:?}");
+        }
+
+        let empty = Grid::parse("", |b| b).unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+        assert_eq!(0, empty.rows().count());
+
+        let err = Grid::parse("ab\nc\n", |b| b).unwrap_err();
+        assert_eq!("line 2 is 1 wide, expected 2", err.to_string());
+    }
+
+    #[test]
+    fn indexes_by_position() {
+        let mut grid = Grid::parse("abc\ndef", |b| b as char).unwrap();
+        assert_eq!(Some(&'f'), grid.get((2, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, 2)));
+        *grid.get_mut((0, 1)).unwrap() = 'x';
+        grid[(1, 1)] = 'y';
+        assert_eq!('y', grid[Point::new(1, 1)]);
+        assert_eq!(&['x', 'y', 'f'], grid.row(1));
+        assert_eq!(Some((2, 1)), grid.position(|&c| c == 'f'));
+
+        assert!(grid.contains((2, 1)));
+        assert!(!grid.contains((-1, 0)));
+        assert_eq!(Some((0, 0)), grid.offset((1, 1), (-1, -1)));
+        assert_eq!(None, grid.offset((0, 0), (-1, 0)));
+        assert_eq!(None, grid.offset((2, 0), (1, 0)));
+    }
+
+    #[test]
+    fn iterates_rows_columns_and_cells() {
+        let grid = Grid::parse("abc\ndef", |b| b as char).unwrap();
+        let rows = grid
+            .rows()
+            .map(|row| row.iter().collect())
+            .collect::<Vec<String>>();
+        assert_eq!(vec!["abc", "def"], rows);
+        let columns = grid.columns().map(|c| c.collect()).collect::<Vec<String>>();
+        assert_eq!(vec!["ad", "be", "cf"], columns);
+
+        let cells = grid.iter().map(|(pos, &c)| (pos, c)).collect::<Vec<_>>();
+        assert_eq!(((0, 0), 'a'), cells[0]);
+        assert_eq!(((2, 1), 'f'), cells[5]);
+        assert_eq!(Grid::filled(3, 2, 1), grid.map(|_| 1));
+    }
+
+    #[test]
+    fn yields_neighbours_inside_the_grid() {
+        let grid = Grid::filled(3, 3, ());
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(4, grid.neighbours4((1, 1)).count());
+        assert_eq!(3, grid.neighbours8((2, 2)).count());
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Range, Sub},
+};
+
+/// A set of values stored as disjoint half-open ranges, kept merged so that no two ranges overlap
+/// or touch
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    /// Each range's start to its end
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value in `range`, merging it with any ranges it overlaps or touches
+    pub fn insert(&mut self, range: Range<T>) {
+        let Range { mut start, mut end } = range;
+        if start >= end {
+            return;
+        }
+
+        if let Some((&before, &before_end)) = self.ranges.range(..=start).next_back() {
+            if before_end >= start {
+                self.ranges.remove(&before);
+                start = before;
+                end = end.max(before_end);
+            }
+        }
+        while let Some((&after, &after_end)) = self.ranges.range(start..=end).next() {
+            self.ranges.remove(&after);
+            end = end.max(after_end);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes every value in `range` out of the set, splitting any range it falls inside of
+    pub fn remove(&mut self, range: Range<T>) {
+        let Range { start, end } = range;
+        if start >= end {
+            return;
+        }
+
+        if let Some((&before, &before_end)) = self.ranges.range(..start).next_back() {
+            if before_end > start {
+                self.ranges.insert(before, start);
+                if before_end > end {
+                    self.ranges.insert(end, before_end);
+                }
+            }
+        }
+        while let Some((&inside, &inside_end)) = self.ranges.range(start..end).next() {
+            self.ranges.remove(&inside);
+            if inside_end > end {
+                self.ranges.insert(end, inside_end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// The ranges in ascending order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// How many separate ranges the values make up
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl<T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+    /// How many values are in the set
+    pub fn total_len(&self) -> T {
+        self.iter().fold(T::default(), |total, range| {
+            total + (range.end - range.start)
+        })
+    }
+
+    /// The leftmost range starting before `before` that holds at least `len` values, for
+    /// allocating into first-fit. The whole range is returned, however much of it is needed
+    pub fn first_fit(&self, len: T, before: T) -> Option<Range<T>> {
+        self.ranges
+            .range(..before)
+            .map(|(&start, &end)| start..end)
+            .find(|range| range.end - range.start >= len)
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, ops::Range};
+
+    use quickcheck::QuickCheck;
+
+    use super::RangeSet;
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let mut set = RangeSet::from_iter([0..2, 5..7, 3..4]);
+        assert_eq!(vec![0..2, 3..4, 5..7], set.iter().collect::<Vec<_>>());
+
+        set.insert(2..3);
+        assert_eq!(vec![0..4, 5..7], set.iter().collect::<Vec<_>>());
+        set.insert(1..9);
+        assert_eq!(vec![0..9], set.iter().collect::<Vec<_>>());
+        set.insert(4..4);
+        assert_eq!(1, set.range_count());
+        assert_eq!(9, set.total_len());
+    }
+
+    #[test]
+    fn removes_by_splitting_ranges() {
+        let mut set = RangeSet::from_iter([0..10, 12..15]);
+        set.remove(3..5);
+        assert_eq!(vec![0..3, 5..10, 12..15], set.iter().collect::<Vec<_>>());
+        set.remove(8..13);
+        assert_eq!(vec![0..3, 5..8, 13..15], set.iter().collect::<Vec<_>>());
+        set.remove(0..20);
+        assert!(set.is_empty());
+        assert_eq!(0, set.total_len());
+    }
+
+    #[test]
+    fn finds_the_first_fit_before_a_bound() {
+        let set = RangeSet::from_iter([0..1, 3..5, 8..12]);
+        assert_eq!(Some(0..1), set.first_fit(1, 10));
+        assert_eq!(Some(3..5), set.first_fit(2, 10));
+        assert_eq!(Some(8..12), set.first_fit(3, 9));
+        assert_eq!(None, set.first_fit(3, 8));
+        assert_eq!(None, set.first_fit(5, 20));
+    }
+
+    #[test]
+    fn matches_a_set_of_values() {
+        fn prop(ops: Vec<(bool, u8, u8)>) -> bool {
+            let mut set = RangeSet::new();
+            let mut values = BTreeSet::new();
+            for (insert, start, len) in ops {
+                let (start, end) = (start % 64, start % 64 + len % 16);
+                if insert {
+                    set.insert(start..end);
+                    values.extend(start..end);
+                } else {
+                    set.remove(start..end);
+                    values.retain(|v| !(start..end).contains(v));
+                }
+            }
+
+            let ranges = set.iter().collect::<Vec<Range<u8>>>();
+            let merged = ranges.windows(2).all(|w| w[0].end < w[1].start);
+            let expanded = ranges.iter().cloned().flatten().collect::<BTreeSet<_>>();
+            merged
+                && expanded == values
+                && usize::from(set.total_len()) == values.len()
+                && (0..90).all(|v| set.contains(v) == values.contains(&v))
+        }
+
+        QuickCheck::new()
+            .tests(500)
+            .quickcheck(prop as fn(Vec<(bool, u8, u8)>) -> bool);
+    }
+}
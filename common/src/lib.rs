@@ -0,0 +1,370 @@
+#![allow(dead_code)]
+use std::{
+    cmp::Reverse,
+    fmt::{Debug, Display, Formatter},
+};
+
+use anyhow::{bail, Result};
+use arrayvec::ArrayVec;
+
+#[cfg(feature = "cli")]
+pub mod answers;
+pub mod bitgrid;
+pub mod combinatorics;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod counter;
+pub mod digits;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod intervals;
+pub mod parse;
+pub mod simulate;
+#[cfg(feature = "cli")]
+mod runner;
+#[cfg(feature = "cli")]
+pub mod vault;
+
+// re-exported for the runner macros, so year crates needn't depend on them
+pub use anyhow;
+#[cfg(feature = "cli")]
+pub use clap;
+#[cfg(feature = "cli")]
+pub use runner::*;
+
+/// Day runners need the `cli` feature, without it they build to a stub so the solvers still compile
+#[cfg(not(feature = "cli"))]
+#[macro_export]
+macro_rules! aoc {
+    ($($args:tt)*) => {
+        fn main() {
+            eprintln!("built without the `cli` feature, which day runners need");
+            std::process::exit(1);
+        }
+    };
+}
+
+macro_rules! impl_answer_enum {
+    ( $( ($variant:tt, $ty:ty) ),* ) => {
+        pub enum Answers {
+            $(
+                $variant($ty),
+            )*
+        }
+
+        $(
+            impl From<$ty> for Answers {
+                fn from(t: $ty) -> Self {
+                    Answers::$variant(t)
+                }
+            }
+        )*
+
+        // assumes all types impl Display
+        impl Display for Answers {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        Answers::$variant(t) => write!(f, "{t}"),
+                    )*
+                }
+            }
+        }
+
+        impl Debug for Answers {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        Answers::$variant(t) => write!(f, "{t}"),
+                    )*
+                }
+            }
+        }
+
+        impl Eq for Answers {}
+
+        impl PartialEq for Answers {
+            fn eq(&self, other: &Self) -> bool {
+                let val_self = match self {
+                    $(
+                    Answers::$variant(v) => format!("{v}"),
+                    )*
+                };
+                let val_other = match other {
+                    $(
+                    Answers::$variant(v) => format!("{v}"),
+                    )*
+                };
+                val_self == val_other
+            }
+        }
+    }
+}
+
+impl_answer_enum! {
+    (String, String),
+    (Usize, usize),
+    (U128, u128),
+    (U64, u64),
+    (U32, u32),
+    (U16, u16),
+    (U8, u8),
+    (Isize, isize),
+    (I128, i128),
+    (I64, i64),
+    (I32, i32),
+    (I16, i16),
+    (I8, i8)
+}
+
+impl From<&'_ str> for Answers {
+    fn from(s: &'_ str) -> Self {
+        Answers::String(s.to_string())
+    }
+}
+
+pub trait IntoDayResult: Sized {
+    fn into_result(self) -> Result<DayResult> {
+        Ok(self.into_day_result())
+    }
+    fn into_day_result(self) -> DayResult;
+}
+
+impl IntoDayResult for () {
+    fn into_day_result(self) -> DayResult {
+        DayResult {
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+impl<A> IntoDayResult for A
+where
+    A: Into<Answers>,
+{
+    fn into_day_result(self) -> DayResult {
+        DayResult {
+            part1: Some(self.into()),
+            part2: None,
+        }
+    }
+}
+
+impl<A> IntoDayResult for (A,)
+where
+    A: Into<Answers>,
+{
+    fn into_day_result(self) -> DayResult {
+        let (a,) = self;
+        DayResult {
+            part1: Some(a.into()),
+            part2: None,
+        }
+    }
+}
+
+impl<A, B> IntoDayResult for (A, B)
+where
+    A: Into<Answers>,
+    B: Into<Answers>,
+{
+    fn into_day_result(self) -> DayResult {
+        let (a, b) = self;
+        DayResult {
+            part1: Some(a.into()),
+            part2: Some(b.into()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DayResult {
+    pub part1: Option<Answers>,
+    pub part2: Option<Answers>,
+}
+
+impl Display for DayResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "DayResult {{")?;
+        writeln!(
+            f,
+            "\tpart 1: {p1}",
+            p1 = self
+                .part1
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or("TBC".to_string())
+        )?;
+        writeln!(
+            f,
+            "\tpart 2: {p2}",
+            p2 = self
+                .part2
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or("TBC".to_string())
+        )?;
+        writeln!(f, "}}")?;
+        Ok(())
+    }
+}
+
+/// Solves one day's puzzle for an input
+pub type Solver = fn(&str) -> Result<DayResult>;
+
+/// Each day a year's crate has solved, so callers can pick the day at runtime
+pub struct Registry(pub &'static [(u8, Solver)]);
+
+impl Registry {
+    pub fn solve(&self, day: u8, input: &str) -> Result<DayResult> {
+        let Some((_, solver)) = self.0.iter().find(|(d, _)| *d == day) else {
+            bail!(
+                "day {day} has no solver, expected one of {days:?}",
+                days = self.days()
+            );
+        };
+
+        solver(input)
+    }
+
+    /// The solved days in ascending order
+    pub fn days(&self) -> Vec<u8> {
+        let mut days = self.0.iter().map(|(day, _)| *day).collect::<Vec<_>>();
+        days.sort_unstable();
+        days
+    }
+}
+
+trait TryConvert {
+    type Into;
+    fn try_convert(self) -> Result<Self::Into, CollectError>;
+}
+
+impl<T, const N: usize> TryConvert for ArrayVec<T, N> {
+    type Into = [T; N];
+
+    fn try_convert(self) -> Result<Self::Into, CollectError> {
+        self.into_inner().map_err(|arr| CollectError {
+            expected: N,
+            actual: arr.len(),
+        })
+    }
+}
+
+trait CollectN<T>
+where
+    Self: Sized,
+{
+    fn try_collect_largest<const N: usize>(self) -> Result<[T; N], CollectError>
+    where
+        T: Ord,
+    {
+        self.collect_smallest().try_convert()
+    }
+
+    fn try_collect_smallest<const N: usize>(self) -> Result<[T; N], CollectError>
+    where
+        T: Ord,
+    {
+        self.collect_largest().try_convert()
+    }
+
+    fn try_collect_by_fn<const N: usize, F>(self, f: F) -> Result<[T; N], CollectError>
+    where
+        F: for<'a> Callable<&'a T>,
+    {
+        self.collect_by_fn(f).try_convert()
+    }
+
+    fn collect_largest<const N: usize>(self) -> ArrayVec<T, N>
+    where
+        T: Ord,
+    {
+        self.collect_by_fn((|v| Reverse(v)) as for<'a> fn(&'a T) -> Reverse<&'a T>)
+    }
+
+    fn collect_smallest<const N: usize>(self) -> ArrayVec<T, N>
+    where
+        T: Ord,
+    {
+        self.collect_by_fn((|v| v) as for<'a> fn(&'a T) -> &'a T)
+    }
+
+    fn collect_by_fn<const N: usize, F>(self, f: F) -> ArrayVec<T, N>
+    where
+        F: for<'a> Callable<&'a T>;
+}
+
+#[derive(Debug)]
+struct CollectError {
+    expected: usize,
+    actual: usize,
+}
+
+impl Display for CollectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CollectError: expected {expected} items, only consumed {actual}",
+            expected = self.expected,
+            actual = self.actual
+        )
+    }
+}
+
+impl std::error::Error for CollectError {}
+
+impl<I, T> CollectN<T> for I
+where
+    I: Iterator<Item = T>,
+{
+    fn collect_by_fn<const N: usize, F>(self, f: F) -> ArrayVec<T, N>
+    where
+        F: for<'a> Callable<&'a T>,
+    {
+        let mut res = ArrayVec::new();
+
+        if N == 0 {
+            return res;
+        }
+
+        let comparer = |a: &_, b: &_| Ord::cmp(&f.call(a), &f.call(b));
+
+        for (i, item) in self.enumerate() {
+            if i >= N {
+                let last = res
+                    .pop()
+                    .expect("there should always be a value as res cap is > 0");
+                let smallest = std::cmp::min_by(item, last, comparer);
+
+                res.push(smallest);
+            } else {
+                res.push(item);
+            }
+
+            res.sort_unstable_by(comparer);
+        }
+
+        res
+    }
+}
+
+trait Callable<T> {
+    type Output: Ord;
+
+    fn call(&self, arg: T) -> Self::Output;
+}
+
+impl<F, T, U> Callable<T> for F
+where
+    U: Ord,
+    F: Fn(T) -> U,
+{
+    type Output = U;
+
+    fn call(&self, arg: T) -> Self::Output {
+        (*self)(arg)
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use nom::{
+    character::complete::{char, digit1, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::Error as NomError,
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, tuple},
+    IResult, Parser,
+};
+
+/// An integer of any type with an optional sign, so `-3` parses as an `i32` but not a `u32`
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Integers separated by spaces or tabs on one line, e.g. `3   4 -1`
+pub fn integers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    delimited(space0, separated_list1(space1, integer), space0)(input)
+}
+
+/// Integers separated by commas with optional spaces, e.g. `75,47, 61`
+pub fn comma_integers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(tuple((space0, char(','), space0)), integer)(input)
+}
+
+/// A `key: values` line such as `190: 10 19`, the values separated by spaces
+pub fn key_values<'a, K, V>(
+    key: impl Parser<&'a str, K, NomError<&'a str>>,
+    value: impl Parser<&'a str, V, NomError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, Vec<V>)> {
+    separated_pair(key, pair(char(':'), space0), separated_list1(space1, value))
+}
+
+/// The blocks of lines separated by blank lines, without their line endings at either end
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        sections.push(&input[start..end]);
+    }
+
+    sections
+}
+
+/// Parses every line of `input` with `parser`, which must consume the whole line, reporting every
+/// line that fails rather than just the first. Trailing blank lines are ignored
+pub fn lines_of<'a, T>(
+    input: &'a str,
+    parser: impl Parser<&'a str, T, NomError<&'a str>>,
+) -> Result<Vec<T>, LinesError> {
+    let mut parser = all_consuming(parser);
+    let mut parsed = vec![];
+    let mut errors = vec![];
+    for (i, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+        match parser.parse(line) {
+            Ok((_, value)) => parsed.push(value),
+            Err(err) => errors.push(LineError::new(i + 1, line, err)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(LinesError(errors))
+    }
+}
+
+/// The lines `lines_of` failed to parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinesError(pub Vec<LineError>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// Counted from 1
+    pub line: usize,
+    /// Counted from 1, in bytes
+    pub column: usize,
+    pub message: String,
+}
+
+impl LineError {
+    fn new(line: usize, text: &str, err: nom::Err<NomError<&str>>) -> Self {
+        let (column, message) = match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => (
+                text.len() - err.input.len() + 1,
+                format!("expected {:?} at {:?}", err.code, err.input),
+            ),
+            nom::Err::Incomplete(_) => (text.len() + 1, "unexpected end of line".to_string()),
+        };
+
+        Self {
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {line}, column {column}: {message}",
+            line = self.line,
+            column = self.column,
+            message = self.message
+        )
+    }
+}
+
+impl Display for LinesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [first, rest @ ..] = &self.0[..] else {
+            return write!(f, "failed to parse lines");
+        };
+        write!(f, "{first}")?;
+        if !rest.is_empty() {
+            write!(f, " (and {} more)", rest.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for LinesError {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{character::complete::space1, sequence::separated_pair};
+
+    use super::{comma_integers, integer, integers, key_values, lines_of, sections};
+
+    #[test]
+    fn parses_integers_of_any_type() {
+        assert_eq!(Ok(("", -12i8)), integer::<i8>("-12"));
+        assert_eq!(Ok((" x", 7u128)), integer::<u128>("+7 x"));
+        assert!(integer::<u32>("-12").is_err());
+        assert!(integer::<u8>("256").is_err());
+        assert!(integer::<i32>("x").is_err());
+
+        assert_eq!(Ok(("", vec![3u64, 4, 5])), integers("3   4\t5 "));
+        assert_eq!(Ok(("", vec![-1i32, 2])), integers(" -1 2"));
+        assert_eq!(Ok(("", vec![75usize, 47, 61])), comma_integers("75,47, 61"));
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let mut line = key_values(integer::<u32>, integer::<u32>);
+        assert_eq!(Ok(("", (190, vec![10, 19]))), line("190: 10 19"));
+        assert_eq!(Ok(("", (3, vec![4]))), line("3:4"));
+        assert!(line("190 10 19").is_err());
+    }
+
+    #[test]
+    fn splits_sections_on_blank_lines() {
+        assert_eq!(vec!["a\nb", "c"], sections("a\nb\n\nc\n"));
+        assert_eq!(vec!["a\r\nb", "c"], sections("a\r\nb\r\n\r\nc"));
+        assert_eq!(vec!["a", "b"], sections("\n\na\n \n\n\nb\n\n"));
+        assert!(sections("").is_empty());
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let pair = || separated_pair(integer::<u32>, space1, integer::<u32>);
+        assert_eq!(
+            vec![(1, 2), (3, 4)],
+            lines_of("1 2\r\n3 4\r\n\r\n", pair()).unwrap()
+        );
+
+        let err = lines_of("1 2\n3 x\n5 6\n-7 8\n", pair()).unwrap_err();
+        let lines = err.0.iter().map(|e| (e.line, e.column)).collect::<Vec<_>>();
+        assert_eq!(vec![(2, 3), (4, 1)], lines);
+        assert_eq!(
+            "line 2, column 3: expected Digit at \"x\" (and 1 more)",
+            err.to_string()
+        );
+
+        let err = lines_of("1 2 3\n", pair()).unwrap_err();
+        assert_eq!("line 1, column 4: expected Eof at \" 3\"", err.to_string());
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::{
+    answers::{AnswerStore, Comparison, Recorded},
+    config::Config,
+    vault, DayResult,
+};
+
+pub fn get_input(config: &Config, day: &str, is_test: bool) -> Result<String> {
+    if is_test {
+        let filepath = config.test_inputs_dir().join(format!("{day}.txt"));
+        return std::fs::read_to_string(&filepath)
+            .with_context(|| format!("failed to read {filepath:?}"));
+    }
+
+    let name = config.input_file_name(day);
+    vault::read_cached(config, &name)?
+        .with_context(|| format!("{name} is neither cached nor in the vault"))
+}
+
+/// The real input for a day, for tests run from anywhere in the project
+pub fn real_input(day: &str) -> Result<String> {
+    get_input(&Config::discover()?, day, false)
+}
+
+/// Solves a day of `config`'s year for each profile picked by `args`, checking real inputs against
+/// that profile's answers
+pub fn run_day<F>(config: Config, day: &str, args: &Args, solve: F) -> Result<()>
+where
+    F: Fn(&str, bool) -> Result<DayResult>,
+{
+    let is_test = std::env::var_os("TEST").is_some() || args.test;
+    let configs = match (&args.profile, args.all_profiles) {
+        (_, true) => config.all_profiles()?,
+        (Some(profile), false) => vec![config.with_profile(profile)?],
+        (None, false) => vec![config],
+    };
+
+    let mut mismatched = vec![];
+    for config in &configs {
+        let label = match &config.active_profile {
+            Some(profile) => format!("{day} [{profile}]"),
+            None => day.to_string(),
+        };
+        let input =
+            get_input(config, day, is_test).with_context(|| format!("{label}: no input"))?;
+        let solution = solve(&input, is_test)?;
+
+        println!("{label}: {solution}");
+        if !is_test
+            && check_answers(config, day, &label, &solution, args.record)? == Comparison::Mismatched
+        {
+            mismatched.push(label);
+        }
+    }
+
+    if args.all_profiles && !mismatched.is_empty() {
+        bail!(
+            "answers did not match for {mismatched}",
+            mismatched = mismatched.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Records a real input run & reports whether it agrees with the known answers
+pub fn check_answers(
+    config: &Config,
+    day: &str,
+    label: &str,
+    result: &DayResult,
+    record: bool,
+) -> Result<Comparison> {
+    let store = AnswerStore::new(config, config.year)?;
+    let run = Recorded::from(result);
+    store.record_run(day, &run)?;
+
+    if record {
+        store.record_known(day, &run)?;
+        println!("{label}: recorded as known answers");
+        return Ok(Comparison::Matched);
+    }
+
+    let Some(known) = store.known(day)? else {
+        return Ok(Comparison::Unknown);
+    };
+    let comparison = run.compare(&known);
+    match comparison {
+        Comparison::Unknown => {}
+        Comparison::Matched => println!("{label}: matches known answers"),
+        Comparison::Mismatched => println!(
+            "{label}: does not match known answers (part 1: {p1}, part 2: {p2})",
+            p1 = known.part1.as_deref().unwrap_or("TBC"),
+            p2 = known.part2.as_deref().unwrap_or("TBC"),
+        ),
+    }
+
+    Ok(comparison)
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    #[arg(short, long, default_value_t = false)]
+    pub test: bool,
+    /// Save this run's answers as the known answers for the day
+    #[arg(short, long, default_value_t = false)]
+    pub record: bool,
+    /// The aoc.toml profile whose input & answers to use
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Solve every profile's input, failing if any disagree with that profile's answers
+    #[arg(long, default_value_t = false, conflicts_with_all = ["test", "profile"])]
+    pub all_profiles: bool,
+}
+
+#[macro_export]
+macro_rules! aoc_args_input_only {
+    ($solver:expr, $a:tt, $b:tt) => {
+        $solver($a)
+    };
+}
+
+#[macro_export]
+macro_rules! aoc_args_both {
+    ($solver:expr, $a:tt, $b:tt) => {
+        $solver($a, $b)
+    };
+}
+
+#[macro_export]
+macro_rules! aoc_impl {
+    ($krate:ident :: $day:ident, $solver:tt) => {
+        use $crate::clap::Parser;
+
+        use $crate::Args;
+        use $krate::days::$day::solve;
+
+        fn main() -> $crate::anyhow::Result<()> {
+            let args = Args::parse();
+            let config = $crate::config::Config::discover()?;
+            $crate::run_day(
+                config,
+                stringify!($day),
+                &args,
+                |input: &str, is_test: bool| $solver!(solve, (input), is_test),
+            )
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! aoc {
+    ($krate:ident :: $day:ident) => {
+        use $crate::aoc_args_input_only;
+        $crate::aoc_impl!($krate::$day, aoc_args_input_only);
+    };
+    ($krate:ident :: $day:ident, is_test) => {
+        use $crate::aoc_args_both;
+        $crate::aoc_impl!($krate::$day, aoc_args_both);
+    };
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the states `x, step(x), step(step(x)), ...` start repeating: the state after `start`
+/// steps comes round again every `len` steps from then on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Period {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Period {
+    /// The earliest step whose state is the same as the state after `n` steps
+    pub fn earliest(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Finds the period of `start` under `step` with Floyd's tortoise & hare, in constant memory.
+/// `step` returning `None` ends the simulation, in which case there's no period
+pub fn floyd<S: Clone + PartialEq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Period> {
+    let mut tortoise = step(&start)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        hare = step(&hare)?;
+    }
+
+    // the hare is now a whole number of periods ahead, so walking both at the same speed from
+    // the start meets at the first repeated state
+    let mut first = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        first += 1;
+    }
+
+    let mut len = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        len += 1;
+    }
+
+    Some(Period { start: first, len })
+}
+
+/// Finds the period of `start` under `step` with Brent's algorithm, in constant memory & usually
+/// fewer steps than `floyd`. `step` returning `None` ends the simulation, in which case there's
+/// no period
+pub fn brent<S: Clone + PartialEq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Period> {
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start)?;
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare)?;
+        len += 1;
+    }
+
+    // with the hare a period ahead of the tortoise they meet at the first repeated state
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..len {
+        hare = step(&hare)?;
+    }
+    let mut first = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        first += 1;
+    }
+
+    Some(Period { start: first, len })
+}
+
+/// The state after `n` steps from `start`, stepping at most once round the period to get there.
+/// `None` if the simulation ends first
+pub fn state_at<S: Clone + PartialEq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    n: usize,
+) -> Option<S> {
+    let n = match brent(start.clone(), &mut step) {
+        Some(period) => period.earliest(n),
+        None => n,
+    };
+
+    (0..n).try_fold(start, |state, _| step(&state))
+}
+
+/// Every state of a simulation up to its first repeat, or to its end
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    states: Vec<S>,
+    period: Option<Period>,
+}
+
+/// Steps from `start` until a state's `key` repeats or `step` returns `None`, remembering every
+/// state on the way. States whose keys are equal must step the same way
+pub fn run_until_repeat<S, K: Hash + Eq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    mut key: impl FnMut(&S) -> K,
+) -> History<S> {
+    let mut seen = HashMap::from([(key(&start), 0)]);
+    let mut states = vec![start];
+    let period = loop {
+        let Some(next) = step(states.last().expect("there's always the start")) else {
+            break None;
+        };
+        let n = states.len();
+        if let Some(&first) = seen.get(&key(&next)) {
+            break Some(Period {
+                start: first,
+                len: n - first,
+            });
+        }
+        seen.insert(key(&next), n);
+        states.push(next);
+    };
+
+    History { states, period }
+}
+
+impl<S> History<S> {
+    /// `None` if the simulation ended without repeating
+    pub fn period(&self) -> Option<Period> {
+        self.period
+    }
+
+    /// The states in the order they were reached, each at most once
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state after `n` steps, or `None` if the simulation ended first
+    pub fn state_at(&self, n: usize) -> Option<&S> {
+        match self.period {
+            Some(period) => self.states.get(period.earliest(n)),
+            None => self.states.get(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, floyd, run_until_repeat, state_at, Period};
+
+    /// `0, 1, .., first - 1` then round `first, .., first + len - 1` forever
+    fn rho(first: usize, len: usize) -> impl Fn(&usize) -> Option<usize> {
+        move |&n| Some(if n + 1 == first + len { first } else { n + 1 })
+    }
+
+    #[test]
+    fn detectors_agree_on_every_small_rho() {
+        for first in 0..8 {
+            for len in 1..8 {
+                let period = Some(Period { start: first, len });
+                assert_eq!(period, floyd(0, rho(first, len)), "{first} {len}");
+                assert_eq!(period, brent(0, rho(first, len)), "{first} {len}");
+                let history = run_until_repeat(0, rho(first, len), |&n| n);
+                assert_eq!(period, history.period());
+                assert_eq!(first + len, history.states().len());
+            }
+        }
+    }
+
+    #[test]
+    fn ending_simulations_have_no_period() {
+        let step = |&n: &u32| (n < 5).then_some(n + 1);
+        assert_eq!(None, floyd(0, step));
+        assert_eq!(None, brent(0, step));
+        assert_eq!(Some(5), state_at(0, step, 5));
+        assert_eq!(None, state_at(0, step, 6));
+
+        let history = run_until_repeat(0, step, |&n| n);
+        assert_eq!(None, history.period());
+        assert_eq!(Some(&5), history.state_at(5));
+        assert_eq!(None, history.state_at(6));
+    }
+
+    #[test]
+    fn jumps_ahead_round_the_period() {
+        let mut steps = 0;
+        let step = |&n: &u64| {
+            steps += 1;
+            Some((n * n + 1) % 1_000)
+        };
+        let far = state_at(3, step, 1_000_000_000_000).unwrap();
+        assert!(steps < 1_000);
+
+        let step = |&n: &u64| Some((n * n + 1) % 1_000);
+        let period = brent(3, step).unwrap();
+        let n = period.earliest(1_000_000_000_000);
+        assert_eq!(Some(far), state_at(3, step, n));
+        assert_eq!(Some(far), state_at(3, step, n + 7 * period.len));
+        let history = run_until_repeat(3, step, |&n| n);
+        assert_eq!(Some(&far), history.state_at(1_000_000_000_000));
+    }
+
+    #[test]
+    fn keys_can_ignore_part_of_the_state() {
+        // the step count rides along in the state but doesn't change how it steps
+        let step = |&(n, steps): &(u32, u32)| Some(((n + 3) % 4, steps + 1));
+        let history = run_until_repeat((0, 0), step, |&(n, _)| n);
+        assert_eq!(Some(Period { start: 0, len: 4 }), history.period());
+        assert_eq!(Some(&(1, 3)), history.state_at(7));
+    }
+}
@@ -0,0 +1,284 @@
+use std::{fmt::Write, io::ErrorKind, path::PathBuf, sync::OnceLock};
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+pub const PASSPHRASE_ENV: &str = "AOC_VAULT_PASSPHRASE";
+const MANIFEST_FILE: &str = "vault.json";
+const EXTENSION: &str = "enc";
+const NONCE_LEN: usize = 24;
+/// Sealed into the manifest so a wrong passphrase is reported as such, not as corrupt files
+const CHECK: &str = "advent of code vault";
+
+/// Encrypted copies of cached inputs & answers, safe to commit as AoC asks that inputs aren't published
+pub struct Vault {
+    root: PathBuf,
+    dir: PathBuf,
+    passphrase: Option<String>,
+    key: OnceLock<Key>,
+}
+
+/// The key derivation salt, shared by every profile so one passphrase opens the whole vault
+#[derive(Serialize, Deserialize, Debug)]
+struct Manifest {
+    salt: String,
+    check: String,
+}
+
+impl Vault {
+    pub fn new(config: &Config) -> Self {
+        Self::at(config.vault_root(), config.vault_dir())
+    }
+
+    fn at(root: PathBuf, dir: PathBuf) -> Self {
+        Self {
+            root,
+            dir,
+            passphrase: None,
+            key: OnceLock::new(),
+        }
+    }
+
+    /// Uses `passphrase` instead of reading `AOC_VAULT_PASSPHRASE`
+    pub fn with_passphrase(self, passphrase: String) -> Self {
+        Self {
+            passphrase: Some(passphrase),
+            ..self
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.root.join(MANIFEST_FILE).is_file()
+    }
+
+    /// There's no passphrase to open it with, so reading sealed files will fail
+    pub fn is_locked(&self) -> bool {
+        self.key.get().is_none()
+            && self.passphrase.is_none()
+            && std::env::var_os(PASSPHRASE_ENV).is_none()
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.{EXTENSION}"))
+    }
+
+    /// The names of every file sealed in this profile's part of the vault
+    pub fn names(&self) -> Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err).with_context(|| format!("failed to list {:?}", self.dir)),
+        };
+
+        let mut names = vec![];
+        for entry in entries {
+            let path = entry.context("failed to read vault entry")?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort_unstable();
+
+        Ok(names)
+    }
+
+    /// Decrypts `name`, returning `None` if it was never sealed
+    pub fn read(&self, name: &str) -> Result<Option<String>> {
+        let path = self.path(name);
+        let sealed = match std::fs::read(&path) {
+            Ok(sealed) => sealed,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("failed to read {path:?}")),
+        };
+
+        let plain = self
+            .decrypt(self.key(false)?, name, &sealed)
+            .with_context(|| format!("failed to decrypt {path:?}"))?;
+
+        Ok(Some(plain))
+    }
+
+    /// Encrypts `contents` as `name`, returning false if the vault already held exactly that
+    pub fn write(&self, name: &str, contents: &str) -> Result<bool> {
+        if self.exists() && self.read(name)?.as_deref() == Some(contents) {
+            return Ok(false);
+        }
+
+        let sealed = self.encrypt(self.key(true)?, name, contents)?;
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {:?}", self.dir))?;
+        let path = self.path(name);
+        std::fs::write(&path, sealed).with_context(|| format!("failed to write {path:?}"))?;
+
+        Ok(true)
+    }
+
+    fn key(&self, create: bool) -> Result<&Key> {
+        if let Some(key) = self.key.get() {
+            return Ok(key);
+        }
+
+        let passphrase = match &self.passphrase {
+            Some(passphrase) => passphrase.clone(),
+            None => std::env::var(PASSPHRASE_ENV)
+                .with_context(|| format!("the vault is locked, set {PASSPHRASE_ENV}"))?,
+        };
+        let path = self.root.join(MANIFEST_FILE);
+        let key = match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                let manifest: Manifest = serde_json::from_str(&contents)
+                    .with_context(|| format!("failed to parse {path:?}"))?;
+                let key = derive_key(&passphrase, &from_hex(&manifest.salt)?)?;
+                self.decrypt(&key, MANIFEST_FILE, &from_hex(&manifest.check)?)
+                    .context("wrong vault passphrase")?;
+                key
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound && create => {
+                let mut salt = [0; 16];
+                OsRng.fill_bytes(&mut salt);
+                let key = derive_key(&passphrase, &salt)?;
+                let manifest = Manifest {
+                    salt: to_hex(&salt),
+                    check: to_hex(&self.encrypt(&key, MANIFEST_FILE, CHECK)?),
+                };
+                std::fs::create_dir_all(&self.root)
+                    .with_context(|| format!("failed to create {:?}", self.root))?;
+                let contents =
+                    serde_json::to_string_pretty(&manifest).context("failed to serialise")?;
+                std::fs::write(&path, contents)
+                    .with_context(|| format!("failed to write {path:?}"))?;
+                key
+            }
+            Err(err) => return Err(err).with_context(|| format!("failed to read {path:?}")),
+        };
+
+        Ok(self.key.get_or_init(|| key))
+    }
+
+    /// Seals `contents` as `nonce || ciphertext`, authenticating the name so files can't be swapped
+    fn encrypt(&self, key: &Key, name: &str, contents: &str) -> Result<Vec<u8>> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: contents.as_bytes(),
+            aad: name.as_bytes(),
+        };
+        let ciphertext = XChaCha20Poly1305::new(key)
+            .encrypt(&nonce, payload)
+            .map_err(|_| anyhow!("failed to encrypt {name}"))?;
+
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    fn decrypt(&self, key: &Key, name: &str, sealed: &[u8]) -> Result<String> {
+        if sealed.len() < NONCE_LEN {
+            bail!("{name} is truncated");
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: name.as_bytes(),
+        };
+        let plain = XChaCha20Poly1305::new(key)
+            .decrypt(XNonce::from_slice(nonce), payload)
+            .map_err(|_| anyhow!("{name} is corrupt or was sealed with another passphrase"))?;
+
+        String::from_utf8(plain).with_context(|| format!("{name} is not utf8"))
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| anyhow!("failed to derive vault key: {err}"))?;
+
+    Ok(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        write!(hex, "{b:02x}").expect("writing to a string cannot fail");
+        hex
+    })
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        bail!("odd length hex {hex:?}");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).with_context(|| format!("bad hex {hex:?}")))
+        .collect()
+}
+
+/// Reads `name` from the cache dir, falling back to the vault when it isn't cached
+pub fn read_cached(config: &Config, name: &str) -> Result<Option<String>> {
+    let path = config.cache_dir()?.join(name);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Vault::new(config).read(name),
+        Err(err) => Err(err).with_context(|| format!("failed to read {path:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_hex, to_hex, Vault};
+
+    fn vault(name: &str) -> Vault {
+        let root = std::env::temp_dir().join(format!("aoc-vault-test-{name}"));
+        let _ = std::fs::remove_dir_all(&root);
+        Vault::at(root.clone(), root.join("work")).with_passphrase("hunter2".to_string())
+    }
+
+    #[test]
+    fn round_trips_and_skips_unchanged_files() {
+        let vault = vault("round-trip");
+        assert_eq!(None, vault.read("2024_day01.txt").unwrap());
+        assert!(vault.write("2024_day01.txt", "3   4\n").unwrap());
+        assert!(!vault.write("2024_day01.txt", "3   4\n").unwrap());
+        assert!(vault.exists());
+        assert!(!vault.is_locked());
+
+        let reopened =
+            Vault::at(vault.root.clone(), vault.dir.clone()).with_passphrase("hunter2".to_string());
+        assert_eq!(
+            Some("3   4\n".to_string()),
+            reopened.read("2024_day01.txt").unwrap()
+        );
+        assert_eq!(vec!["2024_day01.txt"], reopened.names().unwrap());
+    }
+
+    #[test]
+    fn rejects_wrong_passphrase_and_swapped_files() {
+        let vault = vault("tamper");
+        vault.write("2024_day01.txt", "1\n").unwrap();
+        vault.write("2024_day02.txt", "2\n").unwrap();
+
+        let wrong =
+            Vault::at(vault.root.clone(), vault.dir.clone()).with_passphrase("hunter3".to_string());
+        let err = wrong.read("2024_day01.txt").unwrap_err();
+        assert!(format!("{err:#}").contains("wrong vault passphrase"));
+
+        std::fs::copy(vault.path("2024_day02.txt"), vault.path("2024_day01.txt")).unwrap();
+        assert!(vault.read("2024_day01.txt").is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = [0, 1, 0xab, 0xff];
+        assert_eq!("0001abff", to_hex(&bytes));
+        assert_eq!(bytes.to_vec(), from_hex("0001abff").unwrap());
+        assert!(from_hex("abc").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+#ifndef AOC_2024_H
+#define AOC_2024_H
+
+/* Generated from src/lib.rs by build.rs, don't edit by hand */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The answers to one input, or why there are none. Free it with `aoc_result_free`
+ */
+typedef struct AocResult {
+  /**
+   * Part 1's answer, or NULL if it isn't solved yet or `error` is set
+   */
+  char *part1;
+  /**
+   * Part 2's answer, or NULL if it isn't solved yet or `error` is set
+   */
+  char *part2;
+  /**
+   * Why solving failed, or NULL on success
+   */
+  char *error;
+} AocResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves `day` for the `len` bytes of UTF-8 input at `input`, never returning NULL.
+ * A solver that panics is reported through `error` rather than unwinding into the caller
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes, or may be NULL if `len` is 0
+ */
+struct AocResult *aoc_solve(uint8_t day, const uint8_t *input, size_t len);
+
+/**
+ * Frees a result from `aoc_solve` & its strings, doing nothing for NULL
+ *
+ * # Safety
+ *
+ * `result` must come from `aoc_solve` and not have been freed already
+ */
+void aoc_result_free(struct AocResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_2024_H */
@@ -0,0 +1,155 @@
+//! The 2024 solvers behind a C ABI, for checking other implementations against them over FFI.
+//! `include/aoc_2024.h` is generated from this file by the build script, which writes it to
+//! `OUT_DIR`; a test fails when the committed copy is out of date
+
+use std::{
+    ffi::{c_char, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+
+use advent_of_code_2024::{solve_day, Answers, DayResult};
+
+/// The answers to one input, or why there are none. Free it with `aoc_result_free`
+#[repr(C)]
+pub struct AocResult {
+    /// Part 1's answer, or NULL if it isn't solved yet or `error` is set
+    pub part1: *mut c_char,
+    /// Part 2's answer, or NULL if it isn't solved yet or `error` is set
+    pub part2: *mut c_char,
+    /// Why solving failed, or NULL on success
+    pub error: *mut c_char,
+}
+
+/// Solves `day` for the `len` bytes of UTF-8 input at `input`, never returning NULL.
+/// A solver that panics is reported through `error` rather than unwinding into the caller
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or may be NULL if `len` is 0
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u8, input: *const u8, len: usize) -> *mut AocResult {
+    let input = if len == 0 {
+        &[][..]
+    } else {
+        // SAFETY: the caller guarantees `len` readable bytes at `input`
+        unsafe { std::slice::from_raw_parts(input, len) }
+    };
+    let result = match std::str::from_utf8(input) {
+        Ok(input) => solve(day, input),
+        Err(err) => Err(format!("input is not UTF-8: {err}")),
+    };
+
+    Box::into_raw(Box::new(match result {
+        Ok(DayResult { part1, part2 }) => AocResult {
+            part1: answer(part1),
+            part2: answer(part2),
+            error: ptr::null_mut(),
+        },
+        Err(err) => AocResult {
+            part1: ptr::null_mut(),
+            part2: ptr::null_mut(),
+            error: c_string(err),
+        },
+    }))
+}
+
+/// Frees a result from `aoc_solve` & its strings, doing nothing for NULL
+///
+/// # Safety
+///
+/// `result` must come from `aoc_solve` and not have been freed already
+#[no_mangle]
+pub unsafe extern "C" fn aoc_result_free(result: *mut AocResult) {
+    if result.is_null() {
+        return;
+    }
+    // SAFETY: the caller guarantees `result` came from `aoc_solve`, which boxed it
+    let result = unsafe { Box::from_raw(result) };
+    for string in [result.part1, result.part2, result.error] {
+        if !string.is_null() {
+            // SAFETY: every non-NULL string in a result came from `CString::into_raw`
+            drop(unsafe { CString::from_raw(string) });
+        }
+    }
+}
+
+fn solve(day: u8, input: &str) -> Result<DayResult, String> {
+    match catch_unwind(AssertUnwindSafe(|| solve_day(day, input))) {
+        Ok(result) => result.map_err(|err| format!("{err:#}")),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(format!("the solver panicked: {message}"))
+        }
+    }
+}
+
+fn answer(answer: Option<Answers>) -> *mut c_char {
+    answer.map_or(ptr::null_mut(), |answer| c_string(answer.to_string()))
+}
+
+fn c_string(string: String) -> *mut c_char {
+    // interior NULs would truncate the string in C anyway
+    let string = string.replace('\0', "");
+    CString::new(string).expect("NULs are removed").into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::{aoc_result_free, aoc_solve, AocResult};
+
+    fn solve(day: u8, input: &[u8]) -> [Option<String>; 3] {
+        let result = unsafe { aoc_solve(day, input.as_ptr(), input.len()) };
+        let AocResult {
+            part1,
+            part2,
+            error,
+        } = unsafe { &*result };
+        let strings = [*part1, *part2, *error].map(|string| {
+            (!string.is_null()).then(|| {
+                unsafe { CStr::from_ptr(string) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        });
+        unsafe { aoc_result_free(result) };
+        strings
+    }
+
+    #[test]
+    fn committed_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc_2024.h"));
+        let committed = include_str!("../include/aoc_2024.h");
+        assert!(
+            generated.replace("\r\n", "\n") == committed.replace("\r\n", "\n"),
+            "include/aoc_2024.h is out of date, copy {}/aoc_2024.h over it",
+            env!("OUT_DIR")
+        );
+    }
+
+    #[test]
+    fn solves_through_the_c_abi() {
+        let input = include_bytes!("../../test_input/day01.txt");
+        assert_eq!(
+            [Some("11".to_string()), Some("31".to_string()), None],
+            solve(1, input)
+        );
+
+        let [part1, part2, error] = solve(25, input);
+        assert_eq!((None, None), (part1, part2));
+        assert!(error.unwrap().contains("day 25 has no solver"));
+
+        let [_, _, error] = solve(1, b"\xff");
+        assert!(error.unwrap().contains("not UTF-8"));
+
+        let empty = unsafe { aoc_solve(1, std::ptr::null(), 0) };
+        unsafe { aoc_result_free(empty) };
+        unsafe { aoc_result_free(std::ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,63 @@
+//! Compiles `smoke.c` against the generated header & the cdylib with the system C compiler, then
+//! runs it. Skipped when there's no `cc`
+
+#![cfg(unix)]
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+fn compile() -> Option<PathBuf> {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // the cdylib lands next to the `deps` directory holding this test
+    let exe = std::env::current_exe().unwrap();
+    let lib_dir = exe.parent().and_then(Path::parent).unwrap();
+    let smoke = Path::new(env!("CARGO_TARGET_TMPDIR")).join("smoke");
+
+    let status = match Command::new("cc")
+        .arg(crate_dir.join("tests/smoke.c"))
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-laoc_2024", "-Wall", "-Werror", "-o"])
+        .arg(&smoke)
+        .status()
+    {
+        Ok(status) => status,
+        Err(err) => {
+            eprintln!("skipping the C smoke test, failed to run cc: {err}");
+            return None;
+        }
+    };
+    assert!(status.success(), "failed to compile smoke.c");
+
+    Some(smoke)
+}
+
+fn run(smoke: &Path, day: &str, input: &str) -> Output {
+    let input = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../test_input")
+        .join(input);
+    Command::new(smoke).arg(day).arg(input).output().unwrap()
+}
+
+#[test]
+fn solves_from_c() {
+    let Some(smoke) = compile() else {
+        return;
+    };
+
+    let output = run(&smoke, "1", "day01.txt");
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        "part 1: 11\npart 2: 31\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = run(&smoke, "25", "day01.txt");
+    assert_eq!(Some(1), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("day 25 has no solver"));
+}
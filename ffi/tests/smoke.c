@@ -0,0 +1,42 @@
+/* Solves the input file given as argv[2] for day argv[1], printing each part or the error */
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "aoc_2024.h"
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: %s <day> <input>\n", argv[0]);
+        return 2;
+    }
+
+    FILE *file = fopen(argv[2], "rb");
+    if (!file) {
+        perror("failed to open input");
+        return 2;
+    }
+    fseek(file, 0, SEEK_END);
+    long len = ftell(file);
+    rewind(file);
+    char *input = malloc(len);
+    if (fread(input, 1, len, file) != (size_t)len) {
+        perror("failed to read input");
+        return 2;
+    }
+    fclose(file);
+
+    AocResult *result = aoc_solve((uint8_t)atoi(argv[1]), (const uint8_t *)input, (size_t)len);
+    free(input);
+    int status = 0;
+    if (result->error) {
+        printf("error: %s\n", result->error);
+        status = 1;
+    } else {
+        printf("part 1: %s\n", result->part1 ? result->part1 : "not solved yet");
+        printf("part 2: %s\n", result->part2 ? result->part2 : "not solved yet");
+    }
+    aoc_result_free(result);
+    aoc_result_free(NULL);
+
+    return status;
+}
@@ -0,0 +1,88 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+
+use aoc_common::config::Config;
+
+mod serve;
+mod years;
+
+/// Runs a day of any year in the workspace, e.g. `run --year 2023 5 --test`
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// The year to run, defaults to the year of the nearest aoc.toml
+    #[arg(short, long)]
+    year: Option<i32>,
+    /// The day to run
+    #[arg(required = true)]
+    day: Option<u8>,
+    #[command(flatten)]
+    day_args: aoc_common::Args,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serve `POST /{year}/day/{day}`, which solves the input in the request body
+    Serve(serve::ServeArgs),
+    /// Solves one input from stdin & prints the solution as json, used by `serve` to isolate solvers
+    #[command(hide = true)]
+    Worker { year: i32, day: u8 },
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Cli::parse();
+    match args.command {
+        Some(Command::Serve(serve_args)) => serve::serve(serve_args),
+        Some(Command::Worker { year, day }) => serve::worker(year, day),
+        None => {
+            let day = args.day.context("expected a day")?;
+            run(args.year, day, &args.day_args)
+        }
+    }
+}
+
+fn run(year: Option<i32>, day: u8, args: &aoc_common::Args) -> anyhow::Result<()> {
+    let config = Config::discover()?;
+    let year = year.unwrap_or(config.year);
+    let Some(config) = config.years()?.into_iter().find(|c| c.year == year) else {
+        anyhow::bail!("no aoc.toml for {year}, create its crate with `aoc init {year}`");
+    };
+    let registry =
+        years::registry(year).with_context(|| format!("{year} is not in runner/src/years.rs"))?;
+
+    let name = format!("day{day:0>2}");
+    aoc_common::run_day(config, &name, args, |input, _| registry.solve(day, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use super::{Cli, Command};
+
+    #[test]
+    fn args_are_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn day_args_conflict_with_subcommands() {
+        assert!(Cli::try_parse_from(["run", "--year", "2023", "serve"]).is_err());
+        assert!(Cli::try_parse_from(["run", "5", "serve"]).is_err());
+        assert!(Cli::try_parse_from(["run"]).is_err());
+
+        let cli = Cli::try_parse_from(["run", "serve"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Serve(_))));
+        let cli = Cli::try_parse_from(["run", "--year", "2023", "5"]).unwrap();
+        assert_eq!((Some(2023), Some(5)), (cli.year, cli.day));
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    io::{Read, Write},
+    process::{Child, Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use aoc_common::DayResult;
+
+use crate::years;
+
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// The port to listen on
+    #[arg(short, long, default_value_t = 8024)]
+    port: u16,
+    /// The address to listen on, only this machine by default
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+    /// The largest input accepted, in bytes
+    #[arg(long, default_value_t = 1 << 20)]
+    max_input_bytes: usize,
+    /// How long a solver may run before it is killed
+    #[arg(long, default_value_t = 30)]
+    timeout_secs: u64,
+    /// How many inputs may be solved at once, any more are refused until one finishes
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+    max_concurrent: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Part {
+    Answer(String),
+    Error(String),
+}
+
+/// Either each part's answer, or why the solver couldn't get to the parts at all
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+enum Outcome {
+    Parts { part1: Part, part2: Part },
+    Error { error: String },
+}
+
+/// What a worker reports for one input
+#[derive(Serialize, Deserialize, Debug)]
+struct Solved {
+    #[serde(flatten)]
+    outcome: Outcome,
+    solve_ms: f64,
+}
+
+#[derive(Serialize, Debug)]
+struct Solution {
+    year: i32,
+    day: u8,
+    #[serde(flatten)]
+    outcome: Outcome,
+    timings: Timings,
+}
+
+#[derive(Serialize, Debug)]
+struct Timings {
+    /// Time spent in the solver itself
+    solve_ms: f64,
+    /// Time from receiving the input to having the answers, including starting the worker
+    total_ms: f64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// A fixed number of requests that may be in flight at once
+#[derive(Debug)]
+struct Slots {
+    in_use: AtomicUsize,
+    max: usize,
+}
+
+/// Holds one of the `Slots` until dropped
+#[derive(Debug)]
+struct Slot(Arc<Slots>);
+
+impl Slots {
+    fn new(max: usize) -> Arc<Self> {
+        Arc::new(Self {
+            in_use: AtomicUsize::new(0),
+            max,
+        })
+    }
+
+    /// Takes a slot, or `None` if they're all in use
+    fn try_acquire(self: &Arc<Self>) -> Option<Slot> {
+        self.in_use
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Slot(Arc::clone(self)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.in_use.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+pub fn serve(args: ServeArgs) -> anyhow::Result<()> {
+    let server = Server::http((args.host.as_str(), args.port))
+        .map_err(|err| anyhow!("failed to listen on {}:{}: {err}", args.host, args.port))?;
+    println!(
+        "solving POST /{{year}}/day/{{day}} on http://{host}:{port}",
+        host = args.host,
+        port = args.port
+    );
+
+    let slots = Slots::new(args.max_concurrent.into());
+    let args = Arc::new(args);
+    for mut request in server.incoming_requests() {
+        let Some(slot) = slots.try_acquire() else {
+            let busy = HttpError::new(503, "too many inputs being solved, try again shortly");
+            respond(request, Err(busy));
+            continue;
+        };
+        let args = Arc::clone(&args);
+        std::thread::spawn(move || {
+            let _slot = slot;
+            let result = handle(&args, &mut request);
+            respond(request, result);
+        });
+    }
+
+    Ok(())
+}
+
+fn respond(request: Request, result: Result<Solution, HttpError>) {
+    let (status, body) = match result {
+        Ok(solution) => (200, serde_json::to_string(&solution)),
+        Err(err) => (
+            err.status,
+            serde_json::to_string(&serde_json::json!({ "error": err.message })),
+        ),
+    };
+    let body =
+        body.unwrap_or_else(|err| serde_json::json!({ "error": err.to_string() }).to_string());
+    let header = Header::from_bytes("Content-Type", "application/json")
+        .expect("content type header is valid");
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(err) = request.respond(response) {
+        eprintln!("failed to respond: {err}");
+    }
+}
+
+fn handle(args: &ServeArgs, request: &mut Request) -> Result<Solution, HttpError> {
+    let (year, day) = route(request.method(), request.url())?;
+    let registry = years::registry(year)
+        .ok_or_else(|| HttpError::new(404, format!("no solvers for {year}")))?;
+    if !registry.days().contains(&day) {
+        return Err(HttpError::new(
+            404,
+            format!("no solver for {year} day {day}"),
+        ));
+    }
+
+    let too_large = || {
+        HttpError::new(
+            413,
+            format!("inputs are limited to {} bytes", args.max_input_bytes),
+        )
+    };
+    if request
+        .body_length()
+        .is_some_and(|len| len > args.max_input_bytes)
+    {
+        return Err(too_large());
+    }
+    let mut input = vec![];
+    request
+        .as_reader()
+        .take(args.max_input_bytes as u64 + 1)
+        .read_to_end(&mut input)
+        .map_err(|err| HttpError::new(400, format!("failed to read input: {err}")))?;
+    if input.len() > args.max_input_bytes {
+        return Err(too_large());
+    }
+    let input = String::from_utf8(input)
+        .map_err(|err| HttpError::new(400, format!("the input isn't utf-8: {err}")))?;
+
+    let start = Instant::now();
+    let solved = solve_in_worker(year, day, input, Duration::from_secs(args.timeout_secs))?;
+
+    Ok(Solution {
+        year,
+        day,
+        outcome: solved.outcome,
+        timings: Timings {
+            solve_ms: solved.solve_ms,
+            total_ms: start.elapsed().as_secs_f64() * 1_000.0,
+        },
+    })
+}
+
+/// Matches `POST /{year}/day/{day}`
+fn route(method: &Method, url: &str) -> Result<(i32, u8), HttpError> {
+    let not_found = || HttpError::new(404, "expected POST /{year}/day/{day}");
+    let path = url.split('?').next().unwrap_or_default();
+    let ["", year, "day", day] = path.split('/').collect::<Vec<_>>()[..] else {
+        return Err(not_found());
+    };
+    let (Ok(year), Ok(day)) = (year.parse(), day.parse()) else {
+        return Err(not_found());
+    };
+    if *method != Method::Post {
+        return Err(HttpError::new(405, "solve inputs with POST"));
+    }
+
+    Ok((year, day))
+}
+
+/// Solves in a child process, so a solver that hangs or panics can be killed & reported
+fn solve_in_worker(
+    year: i32,
+    day: u8,
+    input: String,
+    timeout: Duration,
+) -> Result<Solved, HttpError> {
+    let internal = |err: anyhow::Error| HttpError::new(500, format!("{err:#}"));
+    let exe = std::env::current_exe()
+        .context("failed to find the runner executable")
+        .map_err(internal)?;
+    let mut child = Command::new(exe)
+        .args(["worker", &year.to_string(), &day.to_string()])
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to start a worker")
+        .map_err(internal)?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = read_in_background(child.stdout.take().expect("stdout is piped"));
+    let stderr = read_in_background(child.stderr.take().expect("stderr is piped"));
+
+    let status = wait_with_timeout(&mut child, timeout)
+        .map_err(internal)?
+        .ok_or_else(|| HttpError::new(504, format!("the solver took over {timeout:?}")))?;
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        // most likely a panic, which takes down both parts
+        return Ok(Solved {
+            outcome: Outcome::Error {
+                error: format!("the solver failed: {}", stderr.trim()),
+            },
+            solve_ms: 0.0,
+        });
+    }
+
+    serde_json::from_str(&stdout)
+        .context("failed to parse the worker's output")
+        .map_err(internal)
+}
+
+fn read_in_background(mut reader: impl Read + Send + 'static) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = reader.read_to_string(&mut output);
+        output
+    })
+}
+
+/// Waits for `child` to exit, killing it & returning `None` if it takes longer than `timeout`
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+) -> anyhow::Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait().context("failed to check on the worker")? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill().context("failed to kill the worker")?;
+            child.wait().context("failed to reap the worker")?;
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+pub fn worker(year: i32, day: u8) -> anyhow::Result<()> {
+    let registry = years::registry(year).with_context(|| format!("no solvers for {year}"))?;
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("failed to read input")?;
+
+    let start = Instant::now();
+    let result = registry.solve(day, &input);
+    let solve_ms = start.elapsed().as_secs_f64() * 1_000.0;
+    let solved = Solved {
+        outcome: outcome(result),
+        solve_ms,
+    };
+    println!("{}", serde_json::to_string(&solved)?);
+
+    Ok(())
+}
+
+/// A part the solver doesn't answer is an error of its own, but a solver error covers both parts
+fn outcome(result: anyhow::Result<DayResult>) -> Outcome {
+    let part = |answer: Option<aoc_common::Answers>| match answer {
+        Some(answer) => Part::Answer(answer.to_string()),
+        None => Part::Error("not solved yet".to_string()),
+    };
+    match result {
+        Ok(DayResult { part1, part2 }) => Outcome::Parts {
+            part1: part(part1),
+            part2: part(part2),
+        },
+        Err(err) => Outcome::Error {
+            error: format!("{err:#}"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use tiny_http::Method;
+
+    use aoc_common::IntoDayResult;
+
+    use super::{outcome, route, Outcome, Part, Slots, Solved};
+
+    #[test]
+    fn routes_posts_to_a_day() {
+        assert_eq!(Ok((2024, 7)), route(&Method::Post, "/2024/day/7"));
+        assert_eq!(Ok((2024, 7)), route(&Method::Post, "/2024/day/7?x=1"));
+        assert_eq!(405, route(&Method::Get, "/2024/day/7").unwrap_err().status);
+        for url in [
+            "/",
+            "/2024/day",
+            "/2024/days/7",
+            "/2024/day/x",
+            "/2024/day/7/1",
+        ] {
+            assert_eq!(404, route(&Method::Post, url).unwrap_err().status, "{url}");
+        }
+    }
+
+    #[test]
+    fn reports_unsolved_parts_per_part_and_solver_errors_once() {
+        assert_eq!(
+            Outcome::Parts {
+                part1: Part::Answer("11".to_string()),
+                part2: Part::Error("not solved yet".to_string())
+            },
+            outcome(Ok((11,).into_day_result()))
+        );
+        assert_eq!(
+            Outcome::Error {
+                error: "bad input".to_string()
+            },
+            outcome(Err(anyhow!("bad input")))
+        );
+    }
+
+    #[test]
+    fn hands_out_a_limited_number_of_slots() {
+        let slots = Slots::new(2);
+        let a = slots.try_acquire().unwrap();
+        let b = slots.try_acquire().unwrap();
+        assert!(slots.try_acquire().is_none());
+        drop(a);
+        let c = slots.try_acquire().unwrap();
+        assert!(slots.try_acquire().is_none());
+        drop((b, c));
+        assert!(slots.try_acquire().is_some());
+    }
+
+    #[test]
+    fn worker_output_round_trips() {
+        for expected in [
+            outcome(Ok((11, "x").into_day_result())),
+            outcome(Err(anyhow!("bad input"))),
+        ] {
+            let json = serde_json::to_string(&Solved {
+                outcome: expected.clone(),
+                solve_ms: 1.5,
+            })
+            .unwrap();
+            let solved = serde_json::from_str::<Solved>(&json).unwrap();
+            assert_eq!(expected, solved.outcome, "{json}");
+        }
+        let json = serde_json::to_string(&Solved {
+            outcome: outcome(Err(anyhow!("bad input"))),
+            solve_ms: 0.0,
+        })
+        .unwrap();
+        assert_eq!(r#"{"error":"bad input","solve_ms":0.0}"#, json);
+    }
+}
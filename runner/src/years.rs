@@ -0,0 +1,14 @@
+use aoc_common::Registry;
+
+/// Every year's solvers, kept in step with the workspace members by `aoc init`
+#[rustfmt::skip]
+pub const YEARS: &[(i32, &Registry)] = &[
+    (2024, &advent_of_code_2024::days::REGISTRY),
+];
+
+pub fn registry(year: i32) -> Option<&'static Registry> {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map(|(_, registry)| *registry)
+}
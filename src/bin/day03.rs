@@ -1 +1 @@
-advent_of_code_2024::aoc!(day03);
+aoc_common::aoc!(advent_of_code_2024::day03);
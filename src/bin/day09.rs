@@ -1 +1 @@
-advent_of_code_2024::aoc!(day09);
\ No newline at end of file
+aoc_common::aoc!(advent_of_code_2024::day09);
\ No newline at end of file
@@ -1,7 +1,12 @@
-use anyhow::{Context, Result};
-use fxhash::FxHashMap as HashMap;
+use anyhow::Result;
+use fxhash::FxBuildHasher;
+use nom::{character::complete::space1, sequence::separated_pair};
 
-use crate::{DayResult, IntoDayResult};
+use crate::{
+    counter::Counter,
+    parse::{integer, lines_of},
+    DayResult, IntoDayResult,
+};
 
 pub fn solve(input: &str) -> Result<DayResult> {
     let (mut a, mut b) = parse_inputs(input)?;
@@ -16,41 +21,16 @@ pub fn solve(input: &str) -> Result<DayResult> {
         .map(|(a, b)| a.abs_diff(b))
         .sum();
 
-    let counts = {
-        let mut counts = HashMap::<usize, usize>::default();
-        for b in b {
-            *counts.entry(b).or_default() += 1_usize;
-        }
-        counts
-    };
+    let counts = b.into_iter().collect::<Counter<_, FxBuildHasher>>();
 
-    let p2: usize = a
-        .into_iter()
-        .map(|a| a * counts.get(&a).cloned().unwrap_or_default())
-        .sum();
+    let p2: usize = a.into_iter().map(|a| a * counts.get(&a) as usize).sum();
 
     (p1, p2).into_result()
 }
 
 fn parse_inputs(s: &str) -> Result<(Vec<usize>, Vec<usize>)> {
-    s.lines()
-        .try_fold((vec![], vec![]), |(mut a, mut b), line| {
-            let mut line = line.split_whitespace();
-            a.push(
-                line.next()
-                    .context("failed to get next from split")?
-                    .parse()
-                    .context("failed to parse usize")?,
-            );
-            b.push(
-                line.next()
-                    .context("failed to get next from split")?
-                    .parse()
-                    .context("failed to parse usize")?,
-            );
-
-            Ok::<_, anyhow::Error>((a, b))
-        })
+    let pairs: Vec<(usize, usize)> = lines_of(s, separated_pair(integer, space1, integer))?;
+    Ok(pairs.into_iter().unzip())
 }
 
 #[cfg(test)]
@@ -66,9 +46,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day01", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day01").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((1_603_498, 25_574_739).into_day_result(), solution);
     }
 }
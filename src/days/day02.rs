@@ -135,9 +135,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day02", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day02").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((257, 328).into_day_result(), solution);
     }
 }
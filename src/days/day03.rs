@@ -62,9 +62,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day03", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day03").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((166_630_675, 93_465_710).into_day_result(), solution);
     }
 }
@@ -1,95 +1,53 @@
-use crate::{DayResult, IntoDayResult};
-use anyhow::{bail, Result};
+use crate::{
+    grid::{Grid, NEIGHBOURS8},
+    DayResult, IntoDayResult,
+};
+use anyhow::Result;
 
 pub fn solve(input: &str) -> Result<DayResult> {
-    let lines = Container::new(input)?;
+    let grid = Grid::parse(input, |b| b)?;
 
-    let p1 = solve_p1(&lines);
-    let p2 = solve_p2(&lines);
+    let p1 = solve_p1(&grid);
+    let p2 = solve_p2(&grid);
 
     (p1, p2).into_result()
 }
 
-struct Container<'a> {
-    source: &'a [u8],
-    width: usize,
-    height: usize,
-}
-
-impl Container<'_> {
-    fn new(source: &str) -> Result<Container> {
-        let source = source.as_bytes();
-        let Some(width) = source.iter().position(|&b| b == b'\n') else {
-            bail!("expected a newline")
-        };
-
-        let height = source.len() / (width + 1);
-
-        Ok(Container {
-            source,
-            width,
-            height,
-        })
-    }
-
-    fn get(&self, x: usize, y: usize) -> Option<u8> {
-        self.source.get(y * (self.width + 1) + x).cloned()
-    }
-}
-
-fn solve_p1(lines: &Container) -> i32 {
-    (0..lines.height)
-        .flat_map(|y| {
-            (0..lines.width).flat_map(move |x| {
-                [
-                    (1, 0),
-                    (-1, 0),
-                    (0, 1),
-                    (0, -1),
-                    (1, 1),
-                    (1, -1),
-                    (-1, 1),
-                    (-1, -1),
-                ]
+fn solve_p1(grid: &Grid<u8>) -> i32 {
+    grid.positions()
+        .flat_map(|pos| {
+            NEIGHBOURS8
                 .into_iter()
-                .map(move |(dx, dy)| check(lines, x, y, dx, dy, "XMAS") as i32)
-            })
+                .map(move |dir| check(grid, Some(pos), dir, "XMAS") as i32)
         })
         .sum()
 }
 
-fn solve_p2(lines: &Container) -> i32 {
-    (0..lines.height)
-        .flat_map(|y| {
-            (0..lines.width).map(move |x| {
-                let leading_mas =
-                    || check(lines, x + 2, y + 2, -1, -1, "MAS") || check(lines, x, y, 1, 1, "MAS");
-                let trailing_mas =
-                    || check(lines, x + 2, y, -1, 1, "MAS") || check(lines, x, y + 2, 1, -1, "MAS");
-                (leading_mas() && trailing_mas()) as i32
-            })
+fn solve_p2(grid: &Grid<u8>) -> i32 {
+    grid.positions()
+        .map(|pos| {
+            let corner = |dx, dy| grid.offset(pos, (dx, dy));
+            let leading_mas = || {
+                check(grid, corner(2, 2), (-1, -1), "MAS") || check(grid, Some(pos), (1, 1), "MAS")
+            };
+            let trailing_mas = || {
+                check(grid, corner(2, 0), (-1, 1), "MAS")
+                    || check(grid, corner(0, 2), (1, -1), "MAS")
+            };
+            (leading_mas() && trailing_mas()) as i32
         })
         .sum()
 }
 
-fn check(lines: &Container, x: usize, y: usize, dx: isize, dy: isize, s: &str) -> bool {
-    s.bytes()
-        .scan((true, x, y), |(cont, x, y), b| {
-            if !*cont {
-                return Some(None);
-            }
-            let res = lines.get(*x, *y).map(|b2| b == b2);
-            match x.checked_add_signed(dx) {
-                Some(nx) => *x = nx,
-                None => *cont = false,
-            }
-            match y.checked_add_signed(dy) {
-                Some(ny) => *y = ny,
-                None => *cont = false,
-            }
-            Some(res)
-        })
-        .all(|ok| ok.unwrap_or(false))
+/// Whether `s` is spelled from `start` stepping by `dir`
+fn check(grid: &Grid<u8>, start: Option<(usize, usize)>, dir: (isize, isize), s: &str) -> bool {
+    let Some(start) = start else {
+        return false;
+    };
+    s.bytes().zip(0..).all(|(b, i)| {
+        grid.offset(start, (dir.0 * i, dir.1 * i))
+            .is_some_and(|pos| grid[pos] == b)
+    })
 }
 
 #[cfg(test)]
@@ -105,9 +63,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day04", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day04").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((2_401, 1_822).into_day_result(), solution);
     }
 }
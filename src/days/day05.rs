@@ -1,19 +1,17 @@
-use crate::{DayResult, IntoDayResult};
-use anyhow::{Context, Result};
+use crate::{
+    parse::{comma_integers, integer, lines_of, sections},
+    DayResult, IntoDayResult,
+};
+use anyhow::{bail, Context, Result};
+use nom::{character::complete::char, sequence::separated_pair};
 
 pub fn solve(input: &str) -> Result<DayResult> {
     let (rules, updates) = parse_rulesets(input)?;
 
     let mut p1 = 0;
     let mut p2 = 0;
-    let mut update = Vec::new();
     let mut counts = [0; 100];
-    for update_str in updates.lines() {
-        update.clear();
-        for num in update_str.split(",") {
-            update.push(num.parse::<usize>()?);
-        }
-
+    for mut update in updates {
         let mut contains = [None; 100];
         for (i, u) in update.iter().enumerate() {
             contains[*u] = Some(i);
@@ -37,11 +35,17 @@ pub fn solve(input: &str) -> Result<DayResult> {
     (p1, p2).into_result()
 }
 
-fn parse_rulesets(input: &str) -> Result<(RuleSets, &str)> {
-    let (rules_str, updates_str) = input.split_once("\n\n").context("no double newline")?;
-    let rules = RuleSets::new(rules_str)?;
+fn parse_rulesets(input: &str) -> Result<(RuleSets, Vec<Vec<usize>>)> {
+    let [rules_str, updates_str] = sections(input)[..] else {
+        bail!("expected rules & updates separated by a blank line");
+    };
+    let rules = RuleSets::new(rules_str).context("bad rules")?;
+    let updates = lines_of(updates_str, comma_integers).context("bad updates")?;
+    if let Some(update) = updates.iter().find(|u| u.iter().any(|&page| page >= 100)) {
+        bail!("pages are expected to be below 100, found {update:?}");
+    }
 
-    Ok((rules, updates_str))
+    Ok((rules, updates))
 }
 
 fn is_success(rules: &RuleSets, update: &[usize], contains: &[Option<usize>; 100]) -> bool {
@@ -71,10 +75,12 @@ impl RuleSets {
         let mut rules_first = std::array::from_fn(|_| Vec::new());
         let mut rules_second = std::array::from_fn(|_| Vec::new());
 
-        for rule in rules_str.lines() {
-            let (first, second) = rule.split_once("|").context("no vertical bar")?;
-            let first: usize = first.parse()?;
-            let second: usize = second.parse()?;
+        let rules: Vec<(usize, usize)> =
+            lines_of(rules_str, separated_pair(integer, char('|'), integer))?;
+        for (first, second) in rules {
+            if first >= 100 || second >= 100 {
+                bail!("pages are expected to be below 100, found {first}|{second}");
+            }
             rules_first[first].push(second);
             rules_second[second].push(first);
         }
@@ -107,9 +113,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day05", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day05").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((4_774, 6_004).into_day_result(), solution);
     }
 }
@@ -1,13 +1,16 @@
-use std::{ops::Add, vec};
-
-use crate::{DayResult, IntoDayResult};
-use anyhow::Result;
+use crate::{
+    bitgrid::{BitGrid, DirBitGrid},
+    geom::{Direction, Point},
+    grid::Grid,
+    DayResult, IntoDayResult,
+};
+use anyhow::{Context, Result};
 
 pub fn solve(input: &str) -> Result<DayResult> {
     let GameInfo {
         mut world,
         position,
-    } = GameInfo::parse(input);
+    } = GameInfo::parse(input)?;
     let mut followed_path = vec![];
     let mut distances = distances(&world);
 
@@ -18,65 +21,61 @@ pub fn solve(input: &str) -> Result<DayResult> {
         &mut followed_path,
         &mut followed_path_dedup,
     );
-    let p2 = solve_p2(
-        position,
-        &mut distances,
-        &mut followed_path,
-        &followed_path_dedup,
-        &mut world,
-    );
+    let p2 = solve_p2(position, &mut distances, &followed_path_dedup, &mut world);
 
     (p1, p2).into_result()
 }
 
 fn solve_p1(
-    position: Coord,
-    distances: &[Vec<Distances>],
-    followed_path: &mut Vec<Coord>,
-    followed_path_dedup: &mut Vec<Coord>,
+    position: Point<usize>,
+    distances: &Grid<Distances>,
+    followed_path: &mut Vec<Point<usize>>,
+    followed_path_dedup: &mut Vec<Point<usize>>,
 ) -> i32 {
     solve_p1_impl(position, distances, followed_path);
-    let mut seen = distances
-        .iter()
-        .map(|l| l.iter().map(|_| false).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let mut seen = BitGrid::new(distances.width(), distances.height());
 
     let mut p1 = 0;
     for c in followed_path {
-        if !seen[c.y][c.x] {
+        if !seen.test_and_set(*c) {
             p1 += 1;
             followed_path_dedup.push(*c);
         }
-        seen[c.y][c.x] = true;
     }
 
     p1
 }
 
-fn solve_p1_impl(mut position: Coord, distances: &[Vec<Distances>], visited: &mut Vec<Coord>) {
+fn solve_p1_impl(
+    mut position: Point<usize>,
+    distances: &Grid<Distances>,
+    visited: &mut Vec<Point<usize>>,
+) {
     visited.clear();
     visited.push(position);
 
-    let mut curr_dir = DxDy { x: 0, y: -1 };
+    let mut curr_dir = Direction::Up;
     loop {
-        let mut dist = distances[position.y][position.x].distance(curr_dir.dir());
+        let mut dist = distances[position].distance(curr_dir);
         while dist == 0 {
-            curr_dir = curr_dir.right();
-            dist = distances[position.y][position.x].distance(curr_dir.dir());
+            curr_dir = curr_dir.turn_right();
+            dist = distances[position].distance(curr_dir);
         }
-        let jump = curr_dir * dist;
-        let Some(new_position) = position + jump else {
+        let jump = curr_dir.vector() * dist as isize;
+        let Some(new_position) = position.checked_add(jump) else {
             let mut c = position;
             loop {
-                let Some(cn) = c + curr_dir else { return };
+                let Some(cn) = c.checked_add(curr_dir.vector()) else {
+                    return;
+                };
                 visited.push(cn);
                 c = cn;
             }
         };
-        if new_position.x >= distances[0].len() {
+        if new_position.x >= distances.width() {
             let mut c = position;
             loop {
-                let cn = (c + curr_dir).unwrap();
+                let cn = (c.checked_add(curr_dir.vector())).unwrap();
                 visited.push(cn);
                 if cn == new_position {
                     visited.pop();
@@ -86,10 +85,10 @@ fn solve_p1_impl(mut position: Coord, distances: &[Vec<Distances>], visited: &mu
             }
             return;
         }
-        if new_position.y >= distances.len() {
+        if new_position.y >= distances.height() {
             let mut c = position;
             loop {
-                let cn = (c + curr_dir).unwrap();
+                let cn = (c.checked_add(curr_dir.vector())).unwrap();
                 visited.push(cn);
                 if cn == new_position {
                     visited.pop();
@@ -101,7 +100,7 @@ fn solve_p1_impl(mut position: Coord, distances: &[Vec<Distances>], visited: &mu
         }
         let mut c = position;
         loop {
-            let cn = (c + curr_dir).unwrap();
+            let cn = (c.checked_add(curr_dir.vector())).unwrap();
             visited.push(cn);
             if cn == new_position {
                 break;
@@ -113,150 +112,124 @@ fn solve_p1_impl(mut position: Coord, distances: &[Vec<Distances>], visited: &mu
 }
 
 fn solve_p2(
-    position: Coord,
-    distances: &mut [Vec<Distances>],
-    followed_path: &mut Vec<Coord>,
-    followed_path_dedup: &[Coord],
-    world: &mut [Vec<bool>],
+    position: Point<usize>,
+    distances: &mut Grid<Distances>,
+    followed_path_dedup: &[Point<usize>],
+    world: &mut Grid<bool>,
 ) -> usize {
-    let mut seen_directional = distances
-        .iter()
-        .map(|d| {
-            d.iter()
-                .map(|_| DirectionalVisited {
-                    up: false,
-                    down: false,
-                    left: false,
-                    right: false,
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+    let mut seen = DirBitGrid::new(world.width(), world.height());
     let mut p2 = 0;
     for &v in followed_path_dedup {
         if v == position {
             continue;
         }
 
-        for vis in followed_path.drain(..) {
-            seen_directional[vis.y][vis.x] = DirectionalVisited {
-                up: false,
-                down: false,
-                left: false,
-                right: false,
-            };
-        }
-
-        world[v.y][v.x] = true;
+        world[v] = true;
         reallocate_distances(world, v, distances);
 
-        if solve_p2_solver(position, distances, followed_path, &mut seen_directional) {
+        if walks_into_loop(position, distances, &mut seen) {
             p2 += 1;
         }
 
-        world[v.y][v.x] = false;
+        world[v] = false;
         reallocate_distances(world, v, distances);
     }
 
     p2
 }
 
-fn reallocate_distances(world: &[Vec<bool>], v: Coord, distances: &mut [Vec<Distances>]) {
+fn reallocate_distances(world: &Grid<bool>, v: Point<usize>, distances: &mut Grid<Distances>) {
     allocate_distances_hori(world, v.y, distances);
     allocate_distances_vert(world, v.x, distances);
 }
 
-fn allocate_distances_vert(world: &[Vec<bool>], x: usize, distances: &mut [Vec<Distances>]) {
+fn allocate_distances_vert(world: &Grid<bool>, x: usize, distances: &mut Grid<Distances>) {
     let mut d = 1;
-    for j in 0..world.len() {
-        if world[j][x] {
-            distances[j][x].down = 0;
+    for (j, &wall) in world.column(x).enumerate() {
+        if wall {
+            distances[(x, j)].up = 0;
             d = 0
         } else {
-            distances[j][x].down = d;
+            distances[(x, j)].up = d;
             d += 1;
         }
     }
     let mut d = 1;
-    for j in (0..world.len()).rev() {
-        if world[j][x] {
-            distances[j][x].up = 0;
+    for j in (0..world.height()).rev() {
+        if world[(x, j)] {
+            distances[(x, j)].down = 0;
             d = 0
         } else {
-            distances[j][x].up = d;
+            distances[(x, j)].down = d;
             d += 1;
         }
     }
 }
 
-fn allocate_distances_hori(world: &[Vec<bool>], y: usize, distances: &mut [Vec<Distances>]) {
+fn allocate_distances_hori(world: &Grid<bool>, y: usize, distances: &mut Grid<Distances>) {
     let mut d = 1;
-    for (i, &wall) in world[y].iter().enumerate() {
+    for (i, &wall) in world.row(y).iter().enumerate() {
         if wall {
-            distances[y][i].left = 0;
+            distances[(i, y)].left = 0;
             d = 0
         } else {
-            distances[y][i].left = d;
+            distances[(i, y)].left = d;
             d += 1;
         }
     }
     let mut d = 1;
-    for (i, &b) in world[y].iter().enumerate().rev() {
+    for (i, &b) in world.row(y).iter().enumerate().rev() {
         if b {
-            distances[y][i].right = 0;
+            distances[(i, y)].right = 0;
             d = 0
         } else {
-            distances[y][i].right = d;
+            distances[(i, y)].right = d;
             d += 1;
         }
     }
 }
 
-fn solve_p2_solver(
-    mut position: Coord,
-    distances: &[Vec<Distances>],
-    followed_path: &mut Vec<Coord>,
-    seen: &mut [Vec<DirectionalVisited>],
+fn walks_into_loop(
+    position: Point<usize>,
+    distances: &Grid<Distances>,
+    seen: &mut DirBitGrid,
 ) -> bool {
-    let mut curr_dir = DxDy { x: 0, y: -1 };
-    followed_path.clear();
-    followed_path.push(position);
-    *seen[position.y][position.x].seen(curr_dir.dir()) = true;
-
-    loop {
-        let mut dist = distances[position.y][position.x].distance(curr_dir.dir());
-        while dist == 0 {
-            curr_dir = curr_dir.right();
-            dist = distances[position.y][position.x].distance(curr_dir.dir());
+    seen.clear();
+    let mut guard = (position, Direction::Up);
+    seen.set(position, Direction::Up);
+    while let Some(next) = step(distances, guard) {
+        if seen.test_and_set(next.0, next.1) {
+            return true;
         }
+        guard = next;
+    }
 
-        let jump = curr_dir * dist;
-        let Some(new_position) = position + jump else {
-            return false;
-        };
-        if new_position.x >= distances[0].len() {
-            return false;
-        }
-        if new_position.y >= distances.len() {
-            return false;
-        }
+    false
+}
 
-        let dir = curr_dir.dir();
-        position = new_position;
-        followed_path.push(position);
-        if std::mem::replace(seen[position.y][position.x].seen(dir), true) {
-            return true;
-        }
+/// Walks the guard from one turn to the next, or `None` once they walk off the map
+fn step(
+    distances: &Grid<Distances>,
+    (position, mut dir): (Point<usize>, Direction),
+) -> Option<(Point<usize>, Direction)> {
+    let mut dist = distances[position].distance(dir);
+    while dist == 0 {
+        dir = dir.turn_right();
+        dist = distances[position].distance(dir);
     }
+
+    let jump = dir.vector() * dist as isize;
+    let position = position.checked_add_within(jump, distances.width(), distances.height())?;
+    Some((position, dir))
 }
 
-fn distances(world: &[Vec<bool>]) -> Vec<Vec<Distances>> {
-    let mut res = vec![vec![Distances::default(); world[0].len()]; world.len()];
+fn distances(world: &Grid<bool>) -> Grid<Distances> {
+    let mut res = Grid::filled(world.width(), world.height(), Distances::default());
 
-    for j in 0..world.len() {
+    for j in 0..world.height() {
         allocate_distances_hori(world, j, &mut res);
     }
-    for i in 0..world[0].len() {
+    for i in 0..world.width() {
         allocate_distances_vert(world, i, &mut res);
     }
 
@@ -264,48 +237,19 @@ fn distances(world: &[Vec<bool>]) -> Vec<Vec<Distances>> {
 }
 
 struct GameInfo {
-    world: Vec<Vec<bool>>,
-    position: Coord,
+    world: Grid<bool>,
+    position: Point<usize>,
 }
 
 impl GameInfo {
-    fn parse(s: &str) -> Self {
-        let mut world = Vec::new();
-        let mut position = Coord { x: 0, y: 0 };
-
-        for (j, line) in s.lines().enumerate() {
-            let mut row = Vec::new();
-            for (i, mut c) in line.chars().enumerate() {
-                if c == '^' {
-                    position = Coord { x: i, y: j };
-                    c = '.';
-                }
-                row.push(c == '#');
-            }
-            world.push(row);
-        }
-
-        Self { world, position }
-    }
-}
-
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
+    fn parse(s: &str) -> Result<Self> {
+        let map = Grid::parse(s, |b| b)?;
+        let (x, y) = map.position(|&b| b == b'^').context("expected a guard")?;
 
-impl Add<DxDy> for Coord {
-    type Output = Option<Coord>;
-
-    fn add(self, rhs: DxDy) -> Self::Output {
-        let Coord { x: x1, y: y1 } = self;
-        let DxDy { x: x2, y: y2 } = rhs;
-
-        let x = x1.checked_add_signed(x2)?;
-        let y = y1.checked_add_signed(y2)?;
-
-        Some(Self { x, y })
+        Ok(Self {
+            world: map.map(|&b| b == b'#'),
+            position: Point::new(x, y),
+        })
     }
 }
 
@@ -318,82 +262,56 @@ struct Distances {
 }
 
 impl Distances {
-    fn distance(&self, dir: Dirs) -> usize {
+    fn distance(&self, dir: Direction) -> usize {
         match dir {
-            Dirs::Up => self.up,
-            Dirs::Down => self.down,
-            Dirs::Left => self.left,
-            Dirs::Right => self.right,
+            Direction::Up => self.up,
+            Direction::Down => self.down,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct DirectionalVisited {
-    up: bool,
-    down: bool,
-    left: bool,
-    right: bool,
-}
-
-impl DirectionalVisited {
-    fn seen(&mut self, dir: Dirs) -> &mut bool {
-        match dir {
-            Dirs::Up => &mut self.up,
-            Dirs::Down => &mut self.down,
-            Dirs::Left => &mut self.left,
-            Dirs::Right => &mut self.right,
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
-struct DxDy {
-    x: isize,
-    y: isize,
-}
-
-impl std::ops::Mul<usize> for DxDy {
-    type Output = DxDy;
+#[cfg(test)]
+mod tests {
+    use super::{distances, reallocate_distances, step, walks_into_loop, GameInfo};
+    use crate::{
+        bitgrid::DirBitGrid,
+        days::day06::solve,
+        geom::{Direction, Point},
+        simulate::brent,
+        IntoDayResult,
+    };
 
-    fn mul(self, rhs: usize) -> Self::Output {
-        let rhs = rhs as isize;
-        let Self { x, y } = self;
-        Self {
-            x: x * rhs,
-            y: y * rhs,
-        }
-    }
-}
+    #[test]
+    fn loop_check_agrees_with_cycle_detection() {
+        const INPUT: &str = include_str!("../../test_input/day06.txt");
+        let GameInfo {
+            mut world,
+            position,
+        } = GameInfo::parse(INPUT).unwrap();
+        let mut distances = distances(&world);
+        let mut seen = DirBitGrid::new(world.width(), world.height());
+
+        let mut loops = 0;
+        for (x, y) in world.positions().collect::<Vec<_>>() {
+            let v = Point::new(x, y);
+            if world[v] || v == position {
+                continue;
+            }
+            world[v] = true;
+            reallocate_distances(&world, v, &mut distances);
 
-impl DxDy {
-    fn right(self) -> Self {
-        let DxDy { x, y } = self;
-        DxDy { x: -y, y: x }
-    }
+            let looped = walks_into_loop(position, &distances, &mut seen);
+            let cycle = brent((position, Direction::Up), |&guard| step(&distances, guard));
+            assert_eq!(cycle.is_some(), looped, "obstacle at {v:?}");
+            loops += usize::from(looped);
 
-    fn dir(self) -> Dirs {
-        match self {
-            DxDy { x: 0, y: 1 } => Dirs::Up,
-            DxDy { x: 0, y: -1 } => Dirs::Down,
-            DxDy { x: 1, y: 0 } => Dirs::Right,
-            DxDy { x: -1, y: 0 } => Dirs::Left,
-            _ => unreachable!("please dont"),
+            world[v] = false;
+            reallocate_distances(&world, v, &mut distances);
         }
+        assert_eq!(6, loops);
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Dirs {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{days::day06::solve, IntoDayResult};
 
     #[test]
     fn works_for_example() {
@@ -404,9 +322,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day06", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day06").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!((5_516, 2_008).into_day_result(), solution);
     }
 }
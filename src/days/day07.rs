@@ -1,18 +1,27 @@
-use std::ops::{Add, Mul};
-
-use crate::{DayResult, IntoDayResult};
+use crate::{
+    digits::Digits,
+    parse::{integer, key_values, lines_of},
+    DayResult, IntoDayResult,
+};
 use anyhow::Result;
-use nom::{bytes::complete::tag, combinator::map, sequence::tuple, IResult};
 
 pub fn solve(input: &str) -> Result<DayResult> {
     let mut entries = parse_entries(input)?;
     let mut p1 = 0;
     let mut p2 = 0;
     for e in entries.iter_mut() {
-        if can_be_solved(e.goal, &mut e.numbers, (Add::add, Mul::mul)) {
+        if can_be_solved(
+            e.goal,
+            &mut e.numbers,
+            (u128::checked_add, u128::checked_mul),
+        ) {
             p1 += e.goal;
         }
-        if can_be_solved(e.goal, &mut e.numbers, (Add::add, Mul::mul, concat)) {
+        if can_be_solved(
+            e.goal,
+            &mut e.numbers,
+            (u128::checked_add, u128::checked_mul, u128::checked_concat),
+        ) {
             p2 += e.goal;
         }
     }
@@ -20,24 +29,11 @@ pub fn solve(input: &str) -> Result<DayResult> {
 }
 
 fn parse_entries(s: &str) -> Result<Vec<Math>> {
-    s.lines()
-        .map(|line| {
-            parse_line(line)
-                .map(|(_, m)| m)
-                .map_err(|err| anyhow::anyhow!("{err}"))
-        })
-        .collect()
-}
-
-fn parse_line(s: &str) -> IResult<&str, Math> {
-    map(
-        tuple((
-            nom::character::complete::u128,
-            tag(": "),
-            nom::multi::separated_list1(tag(" "), nom::character::complete::u128),
-        )),
-        |(goal, _, numbers)| Math { goal, numbers },
-    )(s)
+    let entries = lines_of(s, key_values(integer, integer))?;
+    Ok(entries
+        .into_iter()
+        .map(|(goal, numbers)| Math { goal, numbers })
+        .collect())
 }
 
 #[derive(Debug)]
@@ -48,7 +44,7 @@ struct Math {
 
 fn can_be_solved(goal: u128, numbers: &mut [u128], applicable: impl Applicable) -> bool {
     let [a, rem @ ..] = numbers else {
-        unreachable!("should not be 0 len");
+        return false;
     };
     let a = *a;
     let Some(b) = rem.first().cloned() else {
@@ -62,36 +58,28 @@ fn can_be_solved(goal: u128, numbers: &mut [u128], applicable: impl Applicable)
     applicable.apply(goal, rem, a, b)
 }
 
-fn concat(a: u128, b: u128) -> u128 {
-    if b < 10 {
-        return a * 10 + b;
-    } else if b < 100 {
-        return a * 100 + b;
-    } else if b < 1000 {
-        return a * 1000 + b;
-    }
-    unreachable!("lmao")
-}
-
 trait Applicable {
     fn apply(self, goal: u128, numbers: &mut [u128], a: u128, b: u128) -> bool;
 }
 
 macro_rules! applicable_logic_impl {
     ($self:tt, $goal:tt, $numbers:tt, $a:tt, $b:tt, $f:tt) => {
-        $numbers[0] = $f($a, $b);
-        let first = can_be_solved($goal, $numbers, $self);
-        $numbers[0] = $b;
-        if first {
-            return true;
+        // an operator that overflows can't reach any goal
+        if let Some(combined) = $f($a, $b) {
+            $numbers[0] = combined;
+            let first = can_be_solved($goal, $numbers, $self);
+            $numbers[0] = $b;
+            if first {
+                return true;
+            }
         }
     };
 }
 
 impl<F1, F2> Applicable for (F1, F2)
 where
-    F1: Fn(u128, u128) -> u128 + Copy,
-    F2: Fn(u128, u128) -> u128 + Copy,
+    F1: Fn(u128, u128) -> Option<u128> + Copy,
+    F2: Fn(u128, u128) -> Option<u128> + Copy,
 {
     fn apply(self, goal: u128, numbers: &mut [u128], a: u128, b: u128) -> bool {
         let (f1, f2) = self;
@@ -103,9 +91,9 @@ where
 
 impl<F1, F2, F3> Applicable for (F1, F2, F3)
 where
-    F1: Fn(u128, u128) -> u128 + Copy,
-    F2: Fn(u128, u128) -> u128 + Copy,
-    F3: Fn(u128, u128) -> u128 + Copy,
+    F1: Fn(u128, u128) -> Option<u128> + Copy,
+    F2: Fn(u128, u128) -> Option<u128> + Copy,
+    F3: Fn(u128, u128) -> Option<u128> + Copy,
 {
     fn apply(self, goal: u128, numbers: &mut [u128], a: u128, b: u128) -> bool {
         let (f1, f2, f3) = self;
@@ -129,9 +117,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day07", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day07").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!(
             (1_620_690_235_709_u128, 145_397_611_075_341_u128).into_day_result(),
             solution
@@ -1,4 +1,4 @@
-use crate::{DayResult, IntoDayResult};
+use crate::{intervals::RangeSet, DayResult, IntoDayResult};
 use anyhow::Result;
 
 pub fn solve(input: &str) -> Result<DayResult> {
@@ -26,41 +26,10 @@ pub fn solve(input: &str) -> Result<DayResult> {
 
     let mut p2_final = Vec::new();
     for mut file in p2_files.into_iter().rev() {
-        if let Some((space_index, space)) = p2_spaces
-            .iter()
-            .cloned()
-            .take_while(|space| space.starts_at < file.starts_at)
-            .enumerate()
-            .find(|(_, space)| space.size >= file.size)
-        {
-            p2_spaces.push(Space {
-                starts_at: file.starts_at,
-                size: file.size,
-            });
-            p2_spaces.remove(space_index);
-            let new_space = Space {
-                starts_at: space.starts_at + file.size,
-                size: space.size - file.size,
-            };
-            if new_space.size > 0 {
-                p2_spaces.push(new_space);
-                p2_spaces.sort_unstable_by_key(|f| f.starts_at);
-                let mut i = 0;
-                while i < p2_spaces.len() - 2 {
-                    if p2_spaces[i + 1].starts_at - p2_spaces[i].starts_at == p2_spaces[i].size {
-                        let a = p2_spaces.remove(i);
-                        let b = p2_spaces[i];
-                        let c = Space {
-                            starts_at: a.starts_at,
-                            size: a.size + b.size,
-                        };
-                        p2_spaces[i] = c;
-                    } else {
-                        i += 1;
-                    }
-                }
-            }
-            file.starts_at = space.starts_at;
+        if let Some(space) = p2_spaces.first_fit(file.size, file.starts_at) {
+            p2_spaces.remove(space.start..space.start + file.size);
+            p2_spaces.insert(file.starts_at..file.starts_at + file.size);
+            file.starts_at = space.start;
         }
         p2_final.push(file);
     }
@@ -72,10 +41,10 @@ pub fn solve(input: &str) -> Result<DayResult> {
     (p1, p2).into_result()
 }
 
-fn parse(s: &str) -> (Vec<Option<u128>>, Vec<FileItem>, Vec<Space>) {
+fn parse(s: &str) -> (Vec<Option<u128>>, Vec<FileItem>, RangeSet<usize>) {
     let mut full_system = Vec::new();
     let mut blocks = Vec::new();
-    let mut spaces = Vec::new();
+    let mut spaces = RangeSet::new();
     let mut id = 0;
     let mut is_file = true;
 
@@ -97,10 +66,7 @@ fn parse(s: &str) -> (Vec<Option<u128>>, Vec<FileItem>, Vec<Space>) {
         } else {
             id += 1;
             if n != 0 {
-                spaces.push(Space {
-                    starts_at: full_system.len(),
-                    size: n,
-                });
+                spaces.insert(full_system.len()..full_system.len() + n);
                 full_system.reserve(n);
                 for _ in 0..n {
                     full_system.push(None);
@@ -120,12 +86,6 @@ struct FileItem {
     size: usize,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Space {
-    starts_at: usize,
-    size: usize,
-}
-
 #[cfg(test)]
 mod tests {
     use crate::{days::day09::solve, IntoDayResult};
@@ -139,9 +99,8 @@ mod tests {
 
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day09", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day09").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!(
             (6_607_511_583_593_u128, 6_636_608_781_232_u128).into_day_result(),
             solution
@@ -1,84 +1,54 @@
-use crate::{DayResult, IntoDayResult};
-use anyhow::{Context, Result};
-use fxhash::FxHashMap;
+use crate::{
+    counter::Counter,
+    digits::Digits,
+    parse::{integers, lines_of},
+    DayResult, IntoDayResult,
+};
+use anyhow::{bail, Result};
+use fxhash::FxBuildHasher;
 
 pub fn solve(input: &str) -> Result<DayResult> {
-    let mut stones: FxHashMap<u64, u64> = FxHashMap::default();
-    for stone in input
-        .lines()
-        .next()
-        .context("there is a line")?
-        .split(" ")
-        .map(|n| n.parse())
-    {
-        let stone = stone?;
-        *stones.entry(stone).or_default() += 1;
-    }
-    let mut new_stones = FxHashMap::default();
+    let mut stones: Stones = lines_of(input, integers)?.into_iter().flatten().collect();
+    let mut new_stones = Stones::new();
 
-    blink(25, &mut stones, &mut new_stones);
-    let p1: u64 = stones.values().sum();
-    blink(50, &mut stones, &mut new_stones);
-    let p2: u64 = stones.values().sum();
+    blink(25, &mut stones, &mut new_stones)?;
+    let p1 = stones.total();
+    blink(50, &mut stones, &mut new_stones)?;
+    let p2 = stones.total();
 
     (p1, p2).into_result()
 }
 
-fn blink(range: usize, stones: &mut FxHashMap<u64, u64>, new_stones: &mut FxHashMap<u64, u64>) {
+type Stones = Counter<u64, FxBuildHasher>;
+
+fn blink(range: usize, stones: &mut Stones, new_stones: &mut Stones) -> Result<()> {
     for _ in 0..range {
-        for (stone, count) in stones.drain() {
+        let mut too_big = None;
+        stones.drain_into(new_stones, |stone, count, next| {
             if stone == 0 {
-                *new_stones.entry(1).or_default() += count;
+                next.add_n(1, count);
             } else if let Some((a, b)) = split_in_half(stone) {
-                *new_stones.entry(a).or_default() += count;
-                *new_stones.entry(b).or_default() += count;
+                next.add_n(a, count);
+                next.add_n(b, count);
+            } else if let Some(stone) = stone.checked_mul(2024) {
+                next.add_n(stone, count);
             } else {
-                *new_stones.entry(stone * 2024).or_default() += count;
+                too_big = Some(stone);
             }
+        });
+        if let Some(stone) = too_big {
+            bail!("stone {stone} is too big to multiply by 2024");
         }
-        std::mem::swap(stones, new_stones);
     }
+
+    Ok(())
 }
 
 fn split_in_half(n: u64) -> Option<(u64, u64)> {
-    if n < 10 {
-        return None;
-    } else if n < 100 {
-        return Some((n / 10, n % 10));
-    } else if n < 1_000 {
-        return None;
-    } else if n < 10_000 {
-        return Some((n / 100, n % 100));
-    } else if n < 100_000 {
-        return None;
-    } else if n < 1_000_000 {
-        return Some((n / 1_000, n % 1_000));
-    } else if n < 10_000_000 {
-        return None;
-    } else if n < 100_000_000 {
-        return Some((n / 10_000, n % 10_000));
-    } else if n < 1_000_000_000 {
-        return None;
-    } else if n < 10_000_000_000 {
-        return Some((n / 100_000, n % 100_000));
-    } else if n < 100_000_000_000 {
-        return None;
-    } else if n < 1_000_000_000_000 {
-        return Some((n / 1_000_000, n % 1_000_000));
-    } else if n < 10_000_000_000_000 {
-        return None;
-    } else if n < 100_000_000_000_000 {
-        return Some((n / 10_000_000, n % 10_000_000));
-    } else if n < 1_000_000_000_000_000 {
-        return None;
-    } else if n < 10_000_000_000_000_000 {
-        return Some((n / 100_000_000, n % 100_000_000));
-    } else if n < 100_000_000_000_000_000 {
-        return None;
-    } else if n < 1_000_000_000_000_000_000 {
-        return Some((n / 1_000_000_000, n % 1_000_000_000));
-    }
-    unreachable!("add more cases please")
+    let digits = n.digit_count();
+    digits
+        .is_multiple_of(2)
+        .then(|| n.split_at_digit(digits / 2))
 }
 
 #[cfg(test)]
@@ -92,11 +62,19 @@ mod tests {
         assert_eq!((55_312, 65_601_038_650_482_u64).into_day_result(), solution);
     }
 
+    #[test]
+    fn rejects_stones_that_overflow() {
+        let err = solve("10000000000000000\n").unwrap_err();
+        assert_eq!(
+            "stone 10000000000000000 is too big to multiply by 2024",
+            err.to_string()
+        );
+    }
+
     #[test]
     fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2024_", "day11", ".txt"));
-        let solution = solve(INPUT).unwrap();
+        let input = crate::real_input("day11").unwrap();
+        let solution = solve(&input).unwrap();
         assert_eq!(
             (183_620, 220_377_651_399_268_u64).into_day_result(),
             solution
@@ -9,3 +9,20 @@ pub mod day08;
 pub mod day09;
 pub mod day10;
 pub mod day11;
+
+use aoc_common::Registry;
+
+/// Every day's solver by number, kept in step with the modules above by `aoc`
+pub const REGISTRY: Registry = Registry(&[
+    (1, day01::solve),
+    (2, day02::solve),
+    (3, day03::solve),
+    (4, day04::solve),
+    (5, day05::solve),
+    (6, day06::solve),
+    (7, day07::solve),
+    (8, day08::solve),
+    (9, day09::solve),
+    (10, day10::solve),
+    (11, day11::solve),
+]);